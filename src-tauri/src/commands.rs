@@ -1,22 +1,50 @@
+mod card;
 mod clipboard;
+mod config;
 mod executor;
 mod identifier;
 mod keyboard;
+mod listener;
+mod locale;
+mod note;
+mod opener;
+mod output;
+mod perf;
 mod permission;
+mod plugin;
+mod qr;
+mod schedule;
 mod selection;
+mod selftest;
 mod shortcut;
+mod text;
 mod tray;
 mod typer;
+mod update;
 mod window;
 
 // re-export all command functions
+pub use card::*;
 pub use clipboard::*;
+pub use config::*;
 pub use executor::*;
 pub use identifier::*;
 pub use keyboard::*;
+pub use listener::*;
+pub use locale::*;
+pub use note::*;
+pub use opener::*;
+pub use output::*;
+pub use perf::*;
 pub use permission::*;
+pub use plugin::*;
+pub use qr::*;
+pub use schedule::*;
 pub use selection::*;
+pub use selftest::*;
 pub use shortcut::*;
+pub use text::*;
 pub use tray::*;
 pub use typer::*;
+pub use update::*;
 pub use window::*;
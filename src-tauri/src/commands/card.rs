@@ -0,0 +1,225 @@
+use crate::error::AppError;
+use ab_glyph::{point, Font, FontVec, PxScale, ScaleFont};
+use base64::prelude::*;
+use image::{DynamicImage, ImageFormat, Rgba, RgbaImage};
+use serde::Deserialize;
+use std::io::Cursor;
+
+// candidate system fonts with broad Unicode (including CJK) coverage
+#[cfg(target_os = "macos")]
+const FONT_CANDIDATES: &[&str] = &[
+    "/System/Library/Fonts/Hiragino Sans GB.ttc",
+    "/System/Library/Fonts/PingFang.ttc",
+    "/System/Library/Fonts/Supplemental/Arial Unicode.ttf",
+    "/System/Library/Fonts/Helvetica.ttc",
+];
+#[cfg(not(target_os = "macos"))]
+const FONT_CANDIDATES: &[&str] = &[
+    "C:\\Windows\\Fonts\\msyh.ttc",
+    "C:\\Windows\\Fonts\\simsun.ttc",
+    "C:\\Windows\\Fonts\\segoeui.ttf",
+    "C:\\Windows\\Fonts\\arial.ttf",
+];
+
+// card layout in pixels
+const CARD_WIDTH: u32 = 720;
+const CARD_PADDING: f32 = 56.0;
+const ACCENT_WIDTH: u32 = 6;
+const FONT_SIZE: f32 = 30.0;
+const LINE_SPACING: f32 = 1.5;
+
+// maximum number of characters rendered on a card
+const MAX_CARD_CHARS: usize = 2000;
+
+// maximum number of wrapped lines rendered on a card, keeps the image height bounded
+const MAX_CARD_LINES: usize = 60;
+
+// color theme of the rendered card
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CardTheme {
+    Light,
+    Dark,
+    Sepia,
+}
+
+impl CardTheme {
+    /// Get background, text and accent colors.
+    fn colors(self) -> (Rgba<u8>, Rgba<u8>, Rgba<u8>) {
+        match self {
+            CardTheme::Light => (
+                Rgba([250, 250, 250, 255]),
+                Rgba([33, 33, 33, 255]),
+                Rgba([59, 130, 246, 255]),
+            ),
+            CardTheme::Dark => (
+                Rgba([30, 30, 32, 255]),
+                Rgba([235, 235, 235, 255]),
+                Rgba([129, 140, 248, 255]),
+            ),
+            CardTheme::Sepia => (
+                Rgba([244, 236, 216, 255]),
+                Rgba([91, 70, 54, 255]),
+                Rgba([180, 120, 60, 255]),
+            ),
+        }
+    }
+}
+
+/// Render text on a styled card for sharing.
+/// Text that wraps past `MAX_CARD_LINES` lines is cut off with an ellipsis.
+/// Returns a base64 encoded PNG image.
+#[tauri::command]
+pub fn render_selection_image(text: String, theme: CardTheme) -> Result<String, AppError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("Cannot render image for empty text".into());
+    }
+    if text.chars().count() > MAX_CARD_CHARS {
+        return Err(format!(
+            "Text is too long to render (maximum {} characters)",
+            MAX_CARD_CHARS
+        )
+        .into());
+    }
+
+    let font = load_font()?;
+    let scaled = font.as_scaled(PxScale::from(FONT_SIZE));
+    let (background, foreground, accent) = theme.colors();
+
+    // wrap text into lines that fit the card width, cutting off lines past the cap
+    let max_width = CARD_WIDTH as f32 - CARD_PADDING * 2.0;
+    let mut lines = wrap_lines(&scaled, text, max_width);
+    if lines.len() > MAX_CARD_LINES {
+        lines.truncate(MAX_CARD_LINES);
+        if let Some(last) = lines.last_mut() {
+            append_ellipsis(&scaled, last, max_width);
+        }
+    }
+    let line_height = (scaled.height() * LINE_SPACING).ceil();
+    let height = (CARD_PADDING * 2.0 + line_height * lines.len() as f32).ceil() as u32;
+
+    // draw background and accent bar
+    let mut image = RgbaImage::from_pixel(CARD_WIDTH, height, background);
+    let accent_top = (CARD_PADDING * 0.75) as u32;
+    for y in accent_top..height.saturating_sub(accent_top) {
+        for x in 0..ACCENT_WIDTH {
+            image.put_pixel(CARD_PADDING as u32 / 2 + x, y, accent);
+        }
+    }
+
+    // draw glyphs line by line
+    for (index, line) in lines.iter().enumerate() {
+        let baseline = CARD_PADDING + line_height * index as f32 + scaled.ascent();
+        let mut caret = CARD_PADDING;
+        let mut previous = None;
+        for c in line.chars() {
+            let glyph_id = scaled.glyph_id(c);
+            if let Some(previous) = previous {
+                caret += scaled.kern(previous, glyph_id);
+            }
+            let glyph = glyph_id.with_scale_and_position(scaled.scale(), point(caret, baseline));
+            caret += scaled.h_advance(glyph_id);
+            previous = Some(glyph_id);
+
+            let Some(outlined) = font.outline_glyph(glyph) else {
+                continue;
+            };
+            let bounds = outlined.px_bounds();
+            outlined.draw(|gx, gy, coverage| {
+                let x = bounds.min.x as i32 + gx as i32;
+                let y = bounds.min.y as i32 + gy as i32;
+                if x >= 0 && y >= 0 && (x as u32) < image.width() && (y as u32) < image.height() {
+                    let pixel = image.get_pixel_mut(x as u32, y as u32);
+                    *pixel = blend(*pixel, foreground, coverage);
+                }
+            });
+        }
+    }
+
+    // write PNG into memory buffer
+    let mut png = Vec::new();
+    DynamicImage::ImageRgba8(image)
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .map_err(|e| format!("Failed to write card image: {}", e))?;
+
+    Ok(BASE64_STANDARD.encode(&png))
+}
+
+/// Load the first available system font.
+fn load_font() -> Result<FontVec, AppError> {
+    for path in FONT_CANDIDATES {
+        let Ok(data) = std::fs::read(path) else {
+            continue;
+        };
+        // font collections are loaded from their first face
+        if let Ok(font) = FontVec::try_from_vec_and_index(data, 0) {
+            return Ok(font);
+        }
+    }
+    Err("No usable system font found".into())
+}
+
+/// Wrap text into lines no wider than `max_width`.
+/// Breaks at spaces when possible, otherwise between characters (e.g. CJK text).
+fn wrap_lines<F: Font, SF: ScaleFont<F>>(font: &SF, text: &str, max_width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        let mut width = 0.0;
+        let mut last_space: Option<usize> = None;
+
+        for c in paragraph.chars() {
+            let advance = font.h_advance(font.glyph_id(c));
+            if width + advance > max_width && !line.is_empty() {
+                // move the trailing partial word to the next line
+                let rest = match last_space {
+                    Some(index) => {
+                        let rest = line[index + 1..].to_string();
+                        line.truncate(index);
+                        rest
+                    }
+                    None => String::new(),
+                };
+                lines.push(std::mem::replace(&mut line, rest));
+                width = line.chars().map(|c| font.h_advance(font.glyph_id(c))).sum();
+                last_space = None;
+            }
+            if c == ' ' {
+                last_space = Some(line.len());
+            }
+            line.push(c);
+            width += advance;
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Append an ellipsis to a line, dropping trailing characters until it fits `max_width`.
+fn append_ellipsis<F: Font, SF: ScaleFont<F>>(font: &SF, line: &mut String, max_width: f32) {
+    const ELLIPSIS: char = '…';
+
+    let width = |line: &str| {
+        line.chars()
+            .map(|c| font.h_advance(font.glyph_id(c)))
+            .sum::<f32>()
+    };
+    let ellipsis_width = font.h_advance(font.glyph_id(ELLIPSIS));
+    while !line.is_empty() && width(line) + ellipsis_width > max_width {
+        line.pop();
+    }
+    line.push(ELLIPSIS);
+}
+
+/// Blend a color over a pixel with the given coverage.
+fn blend(base: Rgba<u8>, color: Rgba<u8>, coverage: f32) -> Rgba<u8> {
+    let alpha = coverage.clamp(0.0, 1.0);
+    let mix = |b: u8, c: u8| (b as f32 + (c as f32 - b as f32) * alpha).round() as u8;
+    Rgba([
+        mix(base[0], color[0]),
+        mix(base[1], color[1]),
+        mix(base[2], color[2]),
+        255,
+    ])
+}
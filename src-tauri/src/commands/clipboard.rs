@@ -14,6 +14,10 @@ const ALL_FORMATS: [ContentFormat; 5] = [
     ContentFormat::Files,
 ];
 
+// maximum wait time for clipboard operations dispatched to main thread
+#[cfg(target_os = "macos")]
+const MAIN_THREAD_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(3);
+
 /// Get clipboard text content.
 #[tauri::command]
 pub fn get_clipboard_text() -> Result<String, AppError> {
@@ -35,6 +39,32 @@ pub fn clear_clipboard() -> Result<(), AppError> {
     run(|| Ok(CLIPBOARD.lock()?.as_ref()?.clear()?))
 }
 
+/// Get the content formats currently on the clipboard, such as `Text` or `Image`.
+#[tauri::command]
+pub fn get_clipboard_formats() -> Result<Vec<String>, AppError> {
+    run(|| {
+        let guard = CLIPBOARD.lock()?;
+        let clipboard = guard.as_ref()?;
+        Ok(ALL_FORMATS
+            .iter()
+            .filter(|format| clipboard.has((*format).clone()))
+            .map(|format| format_name(format).to_string())
+            .collect())
+    })
+}
+
+/// Get the display name of a clipboard content format.
+fn format_name(format: &ContentFormat) -> &str {
+    match format {
+        ContentFormat::Text => "Text",
+        ContentFormat::Rtf => "Rtf",
+        ContentFormat::Html => "Html",
+        ContentFormat::Image => "Image",
+        ContentFormat::Files => "Files",
+        ContentFormat::Other(name) => name,
+    }
+}
+
 /// Backup clipboard contents, execute operation, then restore clipboard contents.
 pub async fn with_clipboard_backup<F, Fut, T>(operation: F) -> Result<T, AppError>
 where
@@ -75,15 +105,33 @@ where
 {
     #[cfg(target_os = "macos")]
     {
+        use crate::error::ErrorKind;
         use crate::APP_HANDLE;
+        use log::warn;
+        use std::sync::mpsc::RecvTimeoutError;
+
+        // fail fast if app handle is unavailable (early startup or teardown)
+        let Some(app) = APP_HANDLE.lock()?.clone() else {
+            return Err("App handle is not available".into());
+        };
 
         let (tx, rx) = std::sync::mpsc::channel();
-        if let Some(app) = APP_HANDLE.lock()?.clone() {
-            app.run_on_main_thread(move || {
-                let _ = tx.send(func());
-            })?;
+        app.run_on_main_thread(move || {
+            let _ = tx.send(func());
+        })?;
+
+        // avoid hanging forever if main thread is stuck
+        match rx.recv_timeout(MAIN_THREAD_TIMEOUT) {
+            Ok(result) => result,
+            Err(RecvTimeoutError::Timeout) => {
+                warn!("Clipboard operation timed out waiting for main thread");
+                Err(AppError::with_kind(
+                    ErrorKind::Timeout,
+                    "Timed out waiting for main thread",
+                ))
+            }
+            Err(error) => Err(error.into()),
         }
-        rx.recv()?
     }
 
     #[cfg(not(target_os = "macos"))]
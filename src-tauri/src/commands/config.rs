@@ -0,0 +1,297 @@
+use crate::commands::shortcut::{register_shortcut, unregister_shortcut};
+use crate::error::AppError;
+use crate::{REGISTERED_SHORTCUTS, SETTINGS_STORE};
+use log::{debug, info, warn};
+use serde_json::{Map, Value};
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_store::StoreExt;
+
+/// Expected JSON type of a configuration value.
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    Bool,
+    Number,
+    String,
+    Array,
+    Object,
+}
+
+impl Kind {
+    /// Check if the given value matches this kind.
+    fn matches(self, value: &Value) -> bool {
+        match self {
+            Kind::Bool => value.is_boolean(),
+            Kind::Number => value.is_number(),
+            Kind::String => value.is_string(),
+            Kind::Array => value.is_array(),
+            Kind::Object => value.is_object(),
+        }
+    }
+}
+
+// settings store keys included in configuration export/import
+// API keys and trigger history are intentionally excluded
+const CONFIG_KEYS: &[(&str, Kind)] = &[
+    ("theme", Kind::String),
+    ("shortcuts", Kind::Object),
+    ("blacklist", Kind::Array),
+    ("dndSchedule", Kind::Object),
+    ("autoStart", Kind::Bool),
+    ("autoUpdate", Kind::Bool),
+    ("minimizeToTray", Kind::Bool),
+    ("trayLeftClickAction", Kind::String),
+    ("toolbarMaxActions", Kind::Number),
+    ("toolbarCornerRadius", Kind::Number),
+    ("toolbarOpacity", Kind::Number),
+    ("popupCornerRadius", Kind::Number),
+    ("popupOpacity", Kind::Number),
+    ("popupPinned", Kind::Bool),
+    ("keepPopupOpen", Kind::Bool),
+    ("autoPasteResponse", Kind::Bool),
+    ("popupWindowSize", Kind::Object),
+    ("historySize", Kind::Number),
+    ("longPress", Kind::Bool),
+    ("longPressDuration", Kind::Number),
+    ("iBeamCursor", Kind::Bool),
+    ("releaseModifiersBeforeShortcut", Kind::Bool),
+    ("dismissKey", Kind::String),
+    ("captureHotkey", Kind::String),
+    ("maxSelectionChars", Kind::Number),
+    ("sanitizeSelection", Kind::Bool),
+    ("lastSelectionExpiry", Kind::Number),
+    ("axapiCacheDuration", Kind::Number),
+    ("accessibilityFallback", Kind::Bool),
+    ("boostCapturePriority", Kind::Bool),
+    ("selectionMethodOverrides", Kind::Object),
+    ("copyKeysOverrides", Kind::Object),
+    ("pasteKeysOverrides", Kind::Object),
+    ("editableOverrides", Kind::Object),
+    ("terminalApps", Kind::Array),
+    ("forceFallbackApps", Kind::Array),
+    ("models", Kind::Array),
+    ("regexps", Kind::Array),
+    ("scripts", Kind::Array),
+    ("prompts", Kind::Array),
+    ("searchers", Kind::Array),
+    ("nodePath", Kind::String),
+    ("denoPath", Kind::String),
+    ("pythonPath", Kind::String),
+    ("ollamaHost", Kind::String),
+    ("lmstudioHost", Kind::String),
+];
+
+// current settings store schema version
+const SETTINGS_VERSION: u64 = 2;
+
+// ordered settings migrations, the first one upgrades version 1 to version 2
+const MIGRATIONS: &[fn(&mut Map<String, Value>)] = &[migrate_v1_to_v2];
+
+// mouse gestures are handled by the mouse listener instead of global shortcuts
+const MOUSE_SHORTCUTS: &[&str] = &[
+    "MouseClick+MouseMove",
+    "MouseClick+MouseClick",
+    "Shift+MouseClick",
+    "LongPress",
+];
+
+/// Export settings and rules as a JSON string.
+#[tauri::command]
+pub fn export_config(app: AppHandle) -> Result<String, AppError> {
+    let store = app.store(SETTINGS_STORE)?;
+
+    // collect known keys present in settings store
+    let mut config = Map::new();
+    for (key, _) in CONFIG_KEYS {
+        if let Some(value) = store.get(*key) {
+            config.insert(key.to_string(), value);
+        }
+    }
+
+    Ok(serde_json::to_string_pretty(&Value::Object(config))?)
+}
+
+/// Import settings and rules from a JSON string produced by `export_config`.
+/// Shortcuts are re-registered and all windows are notified to reload.
+#[tauri::command]
+pub fn import_config(app: AppHandle, json: String) -> Result<(), AppError> {
+    // parse and validate configuration structure
+    let config = parse_config(&json)?;
+
+    // unregister current keyboard shortcuts before replacing them
+    if config.contains_key("shortcuts") {
+        let shortcuts: Vec<String> = REGISTERED_SHORTCUTS.lock()?.values().cloned().collect();
+        for shortcut in shortcuts {
+            unregister_shortcut(app.clone(), shortcut).ok();
+        }
+    }
+
+    // write imported values to settings store
+    let store = app.store(SETTINGS_STORE)?;
+    for (key, value) in &config {
+        store.set(key.as_str(), value.clone());
+    }
+    store.save()?;
+
+    // register imported keyboard shortcuts
+    if let Some(Value::Object(shortcuts)) = config.get("shortcuts") {
+        for shortcut in shortcuts.keys() {
+            if MOUSE_SHORTCUTS.contains(&shortcut.as_str()) {
+                continue;
+            }
+            if let Err(error) = register_shortcut(app.clone(), shortcut.clone()) {
+                warn!(
+                    "Failed to register imported shortcut {}: {}",
+                    shortcut, error
+                );
+            }
+        }
+    }
+
+    // notify all windows to reload settings and update tray
+    let _ = app.emit("config-imported", ());
+
+    Ok(())
+}
+
+/// Parse configuration JSON and validate known keys and value types.
+fn parse_config(json: &str) -> Result<Map<String, Value>, AppError> {
+    let Value::Object(config) = serde_json::from_str(json)? else {
+        return Err("Configuration must be a JSON object".into());
+    };
+
+    let mut validated = Map::new();
+    for (key, value) in config {
+        let Some((_, kind)) = CONFIG_KEYS.iter().find(|(k, _)| *k == key) else {
+            debug!("Ignoring unknown configuration key: {}", key);
+            continue;
+        };
+        if !kind.matches(&value) {
+            return Err(format!("Invalid value for configuration key {}", key).into());
+        }
+        validated.insert(key, value);
+    }
+
+    // validate shortcut groups structure
+    if let Some(Value::Object(shortcuts)) = validated.get("shortcuts") {
+        for (shortcut, group) in shortcuts {
+            if !group.get("rules").is_some_and(Value::is_array) {
+                return Err(format!("Invalid rules for shortcut {}", shortcut).into());
+            }
+        }
+    }
+
+    // validate blacklist entries
+    if let Some(Value::Array(blacklist)) = validated.get("blacklist") {
+        if !blacklist.iter().all(Value::is_string) {
+            return Err("Blacklist must only contain strings".into());
+        }
+    }
+
+    Ok(validated)
+}
+
+/// Migrate settings store to the current schema version.
+/// Stores without a `settingsVersion` key are treated as version 1.
+pub(crate) fn migrate_settings(app: &AppHandle) -> Result<(), AppError> {
+    let store = app.store(SETTINGS_STORE)?;
+    let version = store
+        .get("settingsVersion")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(1);
+    if version >= SETTINGS_VERSION {
+        return Ok(());
+    }
+
+    let settings: Map<String, Value> = store.entries().into_iter().collect();
+    let migrated = migrate(settings.clone(), version);
+
+    // write migrated settings back to store
+    for key in settings.keys() {
+        if !migrated.contains_key(key) {
+            store.delete(key);
+        }
+    }
+    for (key, value) in migrated {
+        store.set(key, value);
+    }
+    store.save()?;
+
+    info!(
+        "Migrated settings from version {} to {}",
+        version, SETTINGS_VERSION
+    );
+    Ok(())
+}
+
+/// Apply migrations starting from the given version.
+fn migrate(mut settings: Map<String, Value>, from: u64) -> Map<String, Value> {
+    for migration in MIGRATIONS.iter().skip(from.saturating_sub(1) as usize) {
+        migration(&mut settings);
+    }
+    settings.insert("settingsVersion".to_string(), SETTINGS_VERSION.into());
+    settings
+}
+
+/// Version 1 to 2: drop mistyped values so defaults apply, and clean up blacklist entries.
+fn migrate_v1_to_v2(settings: &mut Map<String, Value>) {
+    settings.retain(|key, value| {
+        !CONFIG_KEYS
+            .iter()
+            .any(|(k, kind)| k == key && !kind.matches(value))
+    });
+
+    if let Some(Value::Array(blacklist)) = settings.get_mut("blacklist") {
+        blacklist.retain(Value::is_string);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn settings(value: Value) -> Map<String, Value> {
+        match value {
+            Value::Object(map) => map,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn migrate_v1_store_to_current() {
+        let v1 = settings(json!({
+            "theme": "dark",
+            "autoStart": "yes",
+            "historySize": "10",
+            "shortcuts": {},
+            "blacklist": ["com.apple.Terminal", 42, null, "*.exe"],
+            "openaiApiKey": "secret",
+        }));
+
+        let migrated = migrate(v1, 1);
+        assert_eq!(
+            migrated,
+            settings(json!({
+                "theme": "dark",
+                "shortcuts": {},
+                "blacklist": ["com.apple.Terminal", "*.exe"],
+                "openaiApiKey": "secret",
+                "settingsVersion": SETTINGS_VERSION,
+            }))
+        );
+    }
+
+    #[test]
+    fn migrate_current_store_is_unchanged() {
+        let current = settings(json!({
+            "theme": "light",
+            "autoStart": true,
+            "blacklist": ["*.exe"],
+            "settingsVersion": SETTINGS_VERSION,
+        }));
+
+        assert_eq!(migrate(current.clone(), SETTINGS_VERSION), current);
+        let migrated = migrate(current.clone(), 1);
+        assert_eq!(migrate(migrated.clone(), SETTINGS_VERSION), migrated);
+    }
+}
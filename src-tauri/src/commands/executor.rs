@@ -1,4 +1,4 @@
-use crate::error::AppError;
+use crate::error::{AppError, ErrorKind};
 use base64::prelude::*;
 use log::debug;
 use serde_json::Value;
@@ -78,6 +78,9 @@ async fn execute_javascript_custom(
     };
     command.stdout(Stdio::piped()).stderr(Stdio::piped());
 
+    // kill the process when execution is cancelled, e.g. by a plugin timeout
+    command.kill_on_drop(true);
+
     // hide console window on Windows
     #[cfg(target_os = "windows")]
     command.creation_flags(CREATE_NO_WINDOW);
@@ -163,6 +166,9 @@ async fn execute_javascript_system(code: &str) -> Result<String, AppError> {
             .stdout(Stdio::piped())
             .stderr(Stdio::piped());
 
+        // kill the process when execution is cancelled, e.g. by a plugin timeout
+        command.kill_on_drop(true);
+
         // hide console window on Windows
         #[cfg(target_os = "windows")]
         command.creation_flags(CREATE_NO_WINDOW);
@@ -188,7 +194,10 @@ async fn execute_javascript_system(code: &str) -> Result<String, AppError> {
         }
     }
 
-    Err("JavaScript runtime not found. Please install Node.js or Deno.".into())
+    Err(AppError::with_kind(
+        ErrorKind::RuntimeMissing,
+        "JavaScript runtime not found. Please install Node.js or Deno.",
+    ))
 }
 
 /// Execute Python code.
@@ -242,6 +251,9 @@ async fn execute_python_custom(program: &str, code: &str) -> Result<String, AppE
     };
     command.stdout(Stdio::piped()).stderr(Stdio::piped());
 
+    // kill the process when execution is cancelled, e.g. by a plugin timeout
+    command.kill_on_drop(true);
+
     // hide console window on Windows
     #[cfg(target_os = "windows")]
     command.creation_flags(CREATE_NO_WINDOW);
@@ -345,6 +357,9 @@ async fn execute_python_system(code: &str) -> Result<String, AppError> {
             .stdout(Stdio::piped())
             .stderr(Stdio::piped());
 
+        // kill the process when execution is cancelled, e.g. by a plugin timeout
+        command.kill_on_drop(true);
+
         // hide console window on Windows
         #[cfg(target_os = "windows")]
         command.creation_flags(CREATE_NO_WINDOW);
@@ -374,7 +389,10 @@ async fn execute_python_system(code: &str) -> Result<String, AppError> {
         }
     }
 
-    Err("Python interpreter not found. Please install Python.".into())
+    Err(AppError::with_kind(
+        ErrorKind::RuntimeMissing,
+        "Python interpreter not found. Please install Python.",
+    ))
 }
 
 /// Execute Shell script.
@@ -405,6 +423,9 @@ pub async fn execute_shell(code: String, data: String) -> Result<String, AppErro
     command.stdin(Stdio::piped());
     command.stdout(Stdio::piped()).stderr(Stdio::piped());
 
+    // kill the process when execution is cancelled, e.g. by a plugin timeout
+    command.kill_on_drop(true);
+
     // hide console window on Windows
     #[cfg(target_os = "windows")]
     command.creation_flags(CREATE_NO_WINDOW);
@@ -475,6 +496,9 @@ if ($__jsonData) {{
         .stdout(Stdio::piped())
         .stderr(Stdio::piped());
 
+    // kill the process when execution is cancelled, e.g. by a plugin timeout
+    command.kill_on_drop(true);
+
     // hide console window on Windows
     #[cfg(target_os = "windows")]
     command.creation_flags(CREATE_NO_WINDOW);
@@ -1,12 +1,101 @@
+use crate::commands::selection::SelectionMethod;
 use crate::error::AppError;
 use crate::platform;
 use crate::SETTINGS_STORE;
 use log::debug;
+use regex::Regex;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::cell::OnceCell;
 use std::path::PathBuf;
-use tauri::AppHandle;
+use tauri::{AppHandle, Url};
 use tauri_plugin_store::StoreExt;
 use wildmatch::WildMatch;
 
+// built-in terminal application patterns
+#[cfg(target_os = "macos")]
+const TERMINAL_APPS: &[&str] = &[
+    "com.apple.Terminal",
+    "com.googlecode.iterm2",
+    "org.alacritty",
+    "net.kovidgoyal.kitty",
+    "com.github.wez.wezterm",
+    "com.mitchellh.ghostty",
+    "dev.warp.Warp*",
+    "co.zeit.hyper",
+];
+#[cfg(not(target_os = "macos"))]
+const TERMINAL_APPS: &[&str] = &[
+    "*\\WindowsTerminal.exe",
+    "*\\cmd.exe",
+    "*\\powershell.exe",
+    "*\\pwsh.exe",
+    "*\\alacritty.exe",
+    "*\\wezterm-gui.exe",
+    "*\\mintty.exe",
+    "*\\Hyper.exe",
+];
+
+// copy and paste shortcuts used by terminals on Windows without overrides
+const TERMINAL_COPY_KEYS: &str = "Control+Shift+KeyC";
+const TERMINAL_PASTE_KEYS: &str = "Control+Shift+KeyV";
+
+// kind of a blacklist rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleKind {
+    App,
+    Website,
+    Domain,
+    Title,
+    Regex,
+}
+
+// structure to hold rule validation result
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleValidation {
+    pub kind: RuleKind,
+    pub valid: bool,
+    pub error: Option<String>,
+}
+
+// structure to hold the result of testing a rule
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleTestResult {
+    pub matches: bool,
+    pub app_id: Option<String>,
+    pub url: Option<String>,
+    pub title: Option<String>,
+}
+
+// lazily queried foreground application information
+#[derive(Default)]
+struct Foreground {
+    app_id: OnceCell<Option<String>>,
+    url: OnceCell<Option<String>>,
+    title: OnceCell<Option<String>>,
+}
+
+impl Foreground {
+    fn app_id(&self) -> Option<&str> {
+        self.app_id
+            .get_or_init(platform::get_frontmost_app_id)
+            .as_deref()
+    }
+
+    fn url(&self) -> Option<&str> {
+        self.url.get_or_init(platform::get_frontmost_url).as_deref()
+    }
+
+    fn title(&self) -> Option<&str> {
+        self.title
+            .get_or_init(platform::get_frontmost_window_title)
+            .as_deref()
+    }
+}
+
 /// Get application identifier from an application path.
 /// - On macOS: Returns the bundle identifier (e.g., "com.apple.Safari")
 /// - On Windows: Returns the normalized executable path (e.g., "C:\\Program Files\\App\\app.exe")
@@ -15,6 +104,13 @@ pub fn get_app_id(app_path: String) -> Result<String, AppError> {
     platform::get_app_id(&PathBuf::from(app_path))
 }
 
+/// Check if two application identifiers refer to the same application.
+/// Both identifiers are normalized the same way `get_app_id` does before comparing.
+#[tauri::command]
+pub fn app_ids_equal(a: String, b: String) -> bool {
+    normalize_app_id(&a) == normalize_app_id(&b)
+}
+
 /// Check if the current frontmost application or website is in the blacklist.
 /// Returns true if any blacklist rule matches, false otherwise.
 #[tauri::command]
@@ -37,46 +133,251 @@ pub fn is_blocked(app: AppHandle) -> Result<bool, AppError> {
         return Ok(false);
     }
 
-    // separate website rules and app rules
-    let (website_rules, app_rules): (Vec<_>, Vec<_>) =
-        blacklist.iter().partition(|rule| is_website_rule(rule));
-
-    // check app rules if any
-    if !app_rules.is_empty() {
-        if let Some(app_id) = platform::get_frontmost_app_id() {
-            debug!("Checking application blacklist for app_id: {}", app_id);
-            for rule in app_rules {
-                if matches_wildcard(rule, &app_id) {
-                    debug!("Application blocked by rule: {}", rule);
-                    return Ok(true);
-                }
-            }
+    // foreground information is only queried when a rule needs it
+    let foreground = Foreground::default();
+    for rule in &blacklist {
+        if rule_matches(rule, &foreground) {
+            debug!("Blocked by rule: {}", rule);
+            return Ok(true);
         }
     }
 
-    // check website rules if any
-    if !website_rules.is_empty() {
-        if let Some(url) = platform::get_frontmost_url() {
-            debug!("Checking website blacklist for url: {}", url);
-            for rule in website_rules {
-                if matches_wildcard(rule.trim_end_matches('/'), url.trim_end_matches('/')) {
-                    debug!("Website blocked by rule: {}", rule);
-                    return Ok(true);
-                }
+    Ok(false)
+}
+
+/// Validate a blacklist rule and report its kind.
+#[tauri::command]
+pub fn validate_rule(rule: String) -> RuleValidation {
+    let (kind, pattern) = parse_rule(&rule);
+
+    let error = if pattern.trim().is_empty() {
+        Some("Rule pattern is empty".to_string())
+    } else if kind == RuleKind::Regex {
+        Regex::new(pattern).err().map(|e| e.to_string())
+    } else if kind == RuleKind::Domain && pattern.contains(['/', ':']) {
+        Some("Domain rule must be a host name".to_string())
+    } else {
+        None
+    };
+
+    RuleValidation {
+        kind,
+        valid: error.is_none(),
+        error,
+    }
+}
+
+/// Test a rule against the current foreground application, website and window.
+#[tauri::command]
+pub fn test_rule_now(rule: String) -> RuleTestResult {
+    let foreground = Foreground::default();
+    let matches = rule_matches(&rule, &foreground);
+
+    RuleTestResult {
+        matches,
+        app_id: foreground.app_id().map(str::to_string),
+        url: foreground.url().map(str::to_string),
+        title: foreground.title().map(str::to_string),
+    }
+}
+
+/// Check if the frontmost application is a terminal.
+/// Built-in terminals can be extended with wildcard patterns in `terminalApps`.
+#[tauri::command]
+pub fn is_terminal_frontmost(app: AppHandle) -> bool {
+    let Some(app_id) = platform::get_frontmost_app_id() else {
+        return false;
+    };
+
+    // get user terminal patterns from settings store
+    let custom: Vec<String> = app
+        .store(SETTINGS_STORE)
+        .ok()
+        .and_then(|store| store.get("terminalApps"))
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+
+    TERMINAL_APPS
+        .iter()
+        .copied()
+        .chain(custom.iter().map(String::as_str))
+        .any(|pattern| matches_wildcard(pattern, &app_id))
+}
+
+/// Check if the frontmost application matches a wildcard pattern in `forceFallbackApps`,
+/// whose native selection is known to return wrong text.
+pub(crate) fn is_force_fallback_app(app: &AppHandle) -> bool {
+    let Some(app_id) = platform::get_frontmost_app_id() else {
+        return false;
+    };
+
+    // get force fallback patterns from settings store
+    let patterns: Vec<String> = app
+        .store(SETTINGS_STORE)
+        .ok()
+        .and_then(|store| store.get("forceFallbackApps"))
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+
+    patterns
+        .iter()
+        .any(|pattern| matches_wildcard(pattern, &app_id))
+}
+
+/// Get the selection capture method configured for the frontmost application.
+/// Terminals use the clipboard method unless overridden.
+/// Returns None if no override rule matches.
+pub(crate) fn get_selection_method_override(app: &AppHandle) -> Option<SelectionMethod> {
+    get_app_override(app, "selectionMethodOverrides")
+        .or_else(|| is_terminal_frontmost(app.clone()).then_some(SelectionMethod::Clipboard))
+}
+
+/// Get the copy shortcut configured for the frontmost application.
+/// Terminals on Windows use Ctrl+Shift+C unless overridden.
+/// Returns None if no override rule matches.
+pub(crate) fn get_copy_keys_override(app: &AppHandle) -> Option<String> {
+    get_app_override(app, "copyKeysOverrides")
+        .or_else(|| get_terminal_keys(app, TERMINAL_COPY_KEYS))
+}
+
+/// Get the paste shortcut configured for the frontmost application.
+/// Terminals on Windows use Ctrl+Shift+V unless overridden.
+/// Returns None if no override rule matches.
+pub(crate) fn get_paste_keys_override(app: &AppHandle) -> Option<String> {
+    get_app_override(app, "pasteKeysOverrides")
+        .or_else(|| get_terminal_keys(app, TERMINAL_PASTE_KEYS))
+}
+
+/// Get the given terminal shortcut if the frontmost application is a terminal.
+/// Terminals on macOS use the regular Cmd+C/V, so this only applies on Windows.
+fn get_terminal_keys(app: &AppHandle, keys: &str) -> Option<String> {
+    if cfg!(target_os = "macos") {
+        return None;
+    }
+    is_terminal_frontmost(app.clone()).then(|| keys.to_string())
+}
+
+/// Get the first override value whose application rule matches the frontmost application.
+fn get_app_override<T: DeserializeOwned>(app: &AppHandle, key: &str) -> Option<T> {
+    // get overrides from settings store
+    let overrides = app.store(SETTINGS_STORE).ok()?.get(key)?;
+    let overrides = overrides.as_object()?;
+    if overrides.is_empty() {
+        return None;
+    }
+
+    let app_id = platform::get_frontmost_app_id()?;
+    for (rule, value) in overrides {
+        if matches_wildcard(rule, &app_id) {
+            if let Ok(value) = serde_json::from_value(value.clone()) {
+                debug!("{} matched by rule: {}", key, rule);
+                return Some(value);
             }
         }
     }
 
-    Ok(false)
+    None
+}
+
+/// Normalize an application identifier for comparison.
+/// - On macOS: Trims the bundle identifier and lowercases it
+/// - On Windows: Canonicalizes the path if it exists, removes the "\\?\" prefix,
+///   unifies separators and lowercases it
+fn normalize_app_id(app_id: &str) -> String {
+    let app_id = app_id.trim();
+
+    #[cfg(not(target_os = "macos"))]
+    let app_id = std::fs::canonicalize(app_id)
+        .ok()
+        .and_then(|path| path.to_str().map(str::to_string))
+        .unwrap_or_else(|| app_id.to_string())
+        .trim_start_matches(r"\\?\")
+        .replace('/', "\\");
+
+    app_id.to_lowercase()
 }
 
-/// Check if a rule is for websites (starts with http:// or https://).
-fn is_website_rule(rule: &str) -> bool {
+/// Split a rule into its kind and the pattern to match.
+/// - `re:<regex>`: regular expression matched against app id, URL and window title
+/// - `title:<wildcard>`: window title rule
+/// - `domain:<host>`: website host rule, `domain:=<host>` excludes subdomains
+/// - `http://`/`https://`: website rule
+/// - anything else: application rule
+fn parse_rule(rule: &str) -> (RuleKind, &str) {
+    if let Some(pattern) = strip_prefix_ignore_case(rule, "re:") {
+        return (RuleKind::Regex, pattern);
+    }
+    if let Some(pattern) = strip_prefix_ignore_case(rule, "title:") {
+        return (RuleKind::Title, pattern);
+    }
+    if let Some(pattern) = strip_prefix_ignore_case(rule, "domain:") {
+        return (RuleKind::Domain, pattern);
+    }
+
     let lower = rule.to_lowercase();
-    lower.starts_with("http://") || lower.starts_with("https://")
+    if lower.starts_with("http://") || lower.starts_with("https://") {
+        (RuleKind::Website, rule)
+    } else {
+        (RuleKind::App, rule)
+    }
+}
+
+/// Check if a rule matches the foreground application, website or window.
+fn rule_matches(rule: &str, foreground: &Foreground) -> bool {
+    let (kind, pattern) = parse_rule(rule);
+    match kind {
+        RuleKind::App => foreground
+            .app_id()
+            .is_some_and(|app_id| matches_wildcard(pattern, app_id)),
+        RuleKind::Website => foreground.url().is_some_and(|url| {
+            matches_wildcard(pattern.trim_end_matches('/'), url.trim_end_matches('/'))
+        }),
+        RuleKind::Domain => foreground
+            .url()
+            .is_some_and(|url| matches_domain(pattern, url)),
+        RuleKind::Title => foreground
+            .title()
+            .is_some_and(|title| matches_wildcard(pattern, title)),
+        RuleKind::Regex => Regex::new(pattern).is_ok_and(|re| {
+            [foreground.app_id(), foreground.url(), foreground.title()]
+                .into_iter()
+                .flatten()
+                .any(|input| re.is_match(input))
+        }),
+    }
+}
+
+/// Check if the host of a URL is the given domain or, unless the domain starts
+/// with `=`, one of its subdomains.
+fn matches_domain(domain: &str, url: &str) -> bool {
+    let Some(host) = Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_lowercase))
+    else {
+        return false;
+    };
+
+    let (domain, exact) = match domain.strip_prefix('=') {
+        Some(domain) => (domain, true),
+        None => (domain, false),
+    };
+    let domain = domain.trim().trim_end_matches('.').to_lowercase();
+
+    host == domain
+        || (!exact
+            && host
+                .strip_suffix(domain.as_str())
+                .is_some_and(|sub| sub.ends_with('.')))
+}
+
+/// Strip an ASCII prefix case-insensitively.
+fn strip_prefix_ignore_case<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    text.get(..prefix.len())
+        .filter(|head| head.eq_ignore_ascii_case(prefix))
+        .map(|_| &text[prefix.len()..])
 }
 
 /// Match a input string against a wildcard pattern.
-fn matches_wildcard(pattern: &str, input: &str) -> bool {
+pub(crate) fn matches_wildcard(pattern: &str, input: &str) -> bool {
     WildMatch::new_case_insensitive(pattern).matches(input)
 }
@@ -1,7 +1,20 @@
-use crate::commands::shortcut::ShortcutHandlerGuard;
+use crate::commands::identifier::get_paste_keys_override;
+use crate::commands::shortcut::{mark_synthetic_input, parse_shortcut, ShortcutHandlerGuard};
 use crate::error::AppError;
-use crate::ENIGO;
+use crate::platform;
+use crate::{ENIGO, RELEASE_MODIFIERS};
 use enigo::{Direction, Key, Keyboard};
+use std::sync::atomic::Ordering;
+use tauri::AppHandle;
+use tauri_plugin_global_shortcut::{Code, Modifiers};
+
+// modifier keys in the order reported by `platform::get_held_modifiers`
+const MODIFIER_KEYS: [Key; 4] = [Key::Meta, Key::Control, Key::Alt, Key::Shift];
+
+// letter keys (`Key::Unicode`) are resolved through the active keyboard layout, so
+// Cmd/Ctrl+X/C/V may misfire on layouts without Latin letters. Copy uses Ctrl+Insert on
+// Windows since it is layout-independent, and per-app overrides in `copyKeysOverrides`
+// and `pasteKeysOverrides` can work around the remaining cases.
 
 /// Send cut shortcut keys.
 #[tauri::command]
@@ -18,9 +31,8 @@ pub fn send_cut_keys(
     let mut enigo_guard = ENIGO.lock()?;
     let enigo = enigo_guard.as_mut()?;
 
-    if release_modifiers.unwrap_or(true) {
-        release_modifier_keys(enigo)?;
-    }
+    mark_synthetic_input();
+    let held = release_modifier_keys(enigo, release_modifiers.unwrap_or(true))?;
 
     // send Cmd+X or Ctrl+X
     #[cfg(target_os = "macos")]
@@ -31,6 +43,7 @@ pub fn send_cut_keys(
     enigo.key(modifier, Direction::Press)?;
     enigo.key(Key::Unicode('x'), Direction::Click)?;
     enigo.key(modifier, Direction::Release)?;
+    restore_modifier_keys(enigo, &held)?;
 
     Ok(())
 }
@@ -50,9 +63,8 @@ pub fn send_copy_keys(
     let mut enigo_guard = ENIGO.lock()?;
     let enigo = enigo_guard.as_mut()?;
 
-    if release_modifiers.unwrap_or(true) {
-        release_modifier_keys(enigo)?;
-    }
+    mark_synthetic_input();
+    let held = release_modifier_keys(enigo, release_modifiers.unwrap_or(true))?;
 
     // send Cmd+C or Ctrl+Insert
     #[cfg(target_os = "macos")]
@@ -63,13 +75,53 @@ pub fn send_copy_keys(
     enigo.key(modifier, Direction::Press)?;
     enigo.key(key, Direction::Click)?;
     enigo.key(modifier, Direction::Release)?;
+    restore_modifier_keys(enigo, &held)?;
+
+    Ok(())
+}
+
+/// Send an arbitrary shortcut such as `Control+Shift+KeyC`.
+/// Only letter, digit and Insert keys are supported.
+pub(crate) fn send_shortcut_keys(shortcut: &str, release_modifiers: bool) -> Result<(), AppError> {
+    let hotkey = parse_shortcut(shortcut)?;
+    let key = code_to_key(hotkey.key)
+        .ok_or_else(|| format!("Unsupported key in shortcut: {}", shortcut))?;
+
+    // collect modifier keys in press order
+    let modifiers: Vec<Key> = [
+        (Modifiers::META, Key::Meta),
+        (Modifiers::CONTROL, Key::Control),
+        (Modifiers::ALT, Key::Alt),
+        (Modifiers::SHIFT, Key::Shift),
+    ]
+    .into_iter()
+    .filter(|(modifier, _)| hotkey.mods.contains(*modifier))
+    .map(|(_, key)| key)
+    .collect();
+
+    let mut enigo_guard = ENIGO.lock()?;
+    let enigo = enigo_guard.as_mut()?;
+
+    mark_synthetic_input();
+    let held = release_modifier_keys(enigo, release_modifiers)?;
+
+    for modifier in &modifiers {
+        enigo.key(*modifier, Direction::Press)?;
+    }
+    enigo.key(key, Direction::Click)?;
+    for modifier in modifiers.iter().rev() {
+        enigo.key(*modifier, Direction::Release)?;
+    }
+    restore_modifier_keys(enigo, &held)?;
 
     Ok(())
 }
 
 /// Send paste shortcut keys.
+/// Uses the per-app paste shortcut from `pasteKeysOverrides` if configured.
 #[tauri::command]
 pub fn send_paste_keys(
+    app: AppHandle,
     suspend_shortcuts: Option<bool>,
     release_modifiers: Option<bool>,
 ) -> Result<(), AppError> {
@@ -79,12 +131,18 @@ pub fn send_paste_keys(
         None
     };
 
+    // use per-app paste shortcut if configured, e.g. Control+Shift+KeyV in terminals
+    if let Some(keys) = get_paste_keys_override(&app) {
+        if send_shortcut_keys(&keys, release_modifiers.unwrap_or(true)).is_ok() {
+            return Ok(());
+        }
+    }
+
     let mut enigo_guard = ENIGO.lock()?;
     let enigo = enigo_guard.as_mut()?;
 
-    if release_modifiers.unwrap_or(true) {
-        release_modifier_keys(enigo)?;
-    }
+    mark_synthetic_input();
+    let held = release_modifier_keys(enigo, release_modifiers.unwrap_or(true))?;
 
     // send Cmd+V or Ctrl+V
     #[cfg(target_os = "macos")]
@@ -95,15 +153,71 @@ pub fn send_paste_keys(
     enigo.key(modifier, Direction::Press)?;
     enigo.key(Key::Unicode('v'), Direction::Click)?;
     enigo.key(modifier, Direction::Release)?;
+    restore_modifier_keys(enigo, &held)?;
 
     Ok(())
 }
 
-/// Release modifier keys to avoid interference.
-fn release_modifier_keys(enigo: &mut dyn Keyboard) -> Result<(), AppError> {
-    enigo.key(Key::Meta, Direction::Release)?;
-    enigo.key(Key::Control, Direction::Release)?;
-    enigo.key(Key::Alt, Direction::Release)?;
-    enigo.key(Key::Shift, Direction::Release)?;
+/// Map a shortcut key code to an enigo key.
+fn code_to_key(code: Code) -> Option<Key> {
+    let name = code.to_string();
+    if let Some(letter) = name.strip_prefix("Key") {
+        return letter
+            .chars()
+            .next()
+            .map(|c| Key::Unicode(c.to_ascii_lowercase()));
+    }
+    if let Some(digit) = name.strip_prefix("Digit") {
+        return digit.chars().next().map(Key::Unicode);
+    }
+    match code {
+        Code::Insert => Some(Key::Insert),
+        _ => None,
+    }
+}
+
+/// Set whether modifier keys are released before sending shortcuts.
+#[tauri::command]
+pub fn set_release_modifiers_enabled(enabled: bool) -> Result<(), AppError> {
+    RELEASE_MODIFIERS.store(enabled, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Release modifier keys to avoid interference, returning the ones physically held.
+/// Nothing is released when disabled by the `releaseModifiersBeforeShortcut` setting.
+fn release_modifier_keys(enigo: &mut dyn Keyboard, release: bool) -> Result<Vec<Key>, AppError> {
+    if !release || !RELEASE_MODIFIERS.load(Ordering::Relaxed) {
+        return Ok(Vec::new());
+    }
+
+    let held = MODIFIER_KEYS
+        .into_iter()
+        .zip(platform::get_held_modifiers())
+        .filter(|(_, held)| *held)
+        .map(|(key, _)| key)
+        .collect();
+
+    for key in MODIFIER_KEYS {
+        enigo.key(key, Direction::Release)?;
+    }
+
+    Ok(held)
+}
+
+/// Press modifier keys again that were held before being released.
+/// Keys let go while the shortcut was sent are skipped, otherwise they would be left stuck.
+fn restore_modifier_keys(enigo: &mut dyn Keyboard, held: &[Key]) -> Result<(), AppError> {
+    if held.is_empty() {
+        return Ok(());
+    }
+
+    let still_held = platform::get_held_modifiers();
+    for (key, _) in MODIFIER_KEYS
+        .into_iter()
+        .zip(still_held)
+        .filter(|(key, still_held)| *still_held && held.contains(key))
+    {
+        enigo.key(key, Direction::Press)?;
+    }
     Ok(())
 }
@@ -0,0 +1,140 @@
+use crate::commands::permission::{check_accessibility, check_input_monitoring};
+use crate::error::AppError;
+use crate::{
+    spawn_input_listener, APP_HANDLE, CAPTURE_HOTKEY, CLIPBOARD, DISMISS_KEY, ENIGO,
+    INPUT_LISTENER_RUNNING, LAST_SELECTION, REGISTERED_SHORTCUTS, SELECTION_TEXT_CACHE,
+    SYNTHETIC_INPUT_UNTIL,
+};
+use enigo::{Enigo, Settings};
+use log::{info, warn};
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::PoisonError;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+// interval between input monitoring permission checks while waiting for it
+const INPUT_MONITORING_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+// whether the mouse listener is waiting for input monitoring permission
+static INPUT_MONITORING_REQUIRED: AtomicBool = AtomicBool::new(false);
+
+// structure to describe what restart_listeners did
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListenersStatus {
+    pub accessibility: bool,
+    pub input_monitoring: bool,
+    pub enigo: bool,
+    pub listener_respawned: bool,
+}
+
+// structure to describe the current state of the mouse listener
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListenerStatus {
+    pub mouse_listener_alive: bool,
+    pub input_monitoring_required: bool,
+}
+
+/// Reinitialize selection listeners and input state without restarting the app.
+/// The mouse listener thread is only respawned if it has stopped.
+#[tauri::command]
+pub fn restart_listeners(app: AppHandle) -> Result<ListenersStatus, AppError> {
+    // re-probe permissions
+    let accessibility = check_accessibility()?;
+    let input_monitoring = check_input_monitoring()?;
+    if !accessibility || !input_monitoring {
+        warn!(
+            "Missing permissions: accessibility={}, input_monitoring={}",
+            accessibility, input_monitoring
+        );
+    }
+
+    // recover state left poisoned by a panicking event handler
+    clear_poisoned_locks();
+
+    // reinitialize keyboard simulation
+    let enigo = {
+        let mut guard = ENIGO.lock().unwrap_or_else(PoisonError::into_inner);
+        *guard = Enigo::new(&Settings::default());
+        guard.is_ok()
+    };
+
+    // reset accessibility API cache
+    #[cfg(target_os = "macos")]
+    crate::platform::reset_axapi_cache();
+
+    // respawn mouse event listener if it has stopped
+    let listener_respawned = spawn_input_listener();
+
+    let status = ListenersStatus {
+        accessibility,
+        input_monitoring,
+        enigo,
+        listener_respawned,
+    };
+    info!("Listeners restarted: {:?}", status);
+
+    app.emit("listeners-restarted", status.clone())?;
+    Ok(status)
+}
+
+/// Clear the poison flag of shared locks used by event handlers.
+/// Panics in handlers are caught, but locks held at that point stay poisoned and every
+/// later `lock()?` would fail until the app restarts. Their data is still consistent enough
+/// to keep using, since each guarded value is replaced as a whole.
+fn clear_poisoned_locks() {
+    APP_HANDLE.clear_poison();
+    ENIGO.clear_poison();
+    CLIPBOARD.clear_poison();
+    SYNTHETIC_INPUT_UNTIL.clear_poison();
+    DISMISS_KEY.clear_poison();
+    CAPTURE_HOTKEY.clear_poison();
+    REGISTERED_SHORTCUTS.clear_poison();
+    SELECTION_TEXT_CACHE.clear_poison();
+    LAST_SELECTION.clear_poison();
+}
+
+/// Check if the mouse event listener thread is running.
+/// Panics in mouse event handlers are caught, so this only turns false if the listener stopped.
+#[tauri::command]
+pub fn is_mouse_listener_alive() -> bool {
+    INPUT_LISTENER_RUNNING.load(Ordering::SeqCst)
+}
+
+/// Get the current state of the mouse event listener.
+/// The UI queries this on mount, since events emitted before its webview loaded are lost.
+#[tauri::command]
+pub fn get_listener_status() -> ListenerStatus {
+    ListenerStatus {
+        mouse_listener_alive: INPUT_LISTENER_RUNNING.load(Ordering::SeqCst),
+        input_monitoring_required: INPUT_MONITORING_REQUIRED.load(Ordering::SeqCst),
+    }
+}
+
+/// Start the mouse event listener once input monitoring permission is granted.
+/// Without the permission the listener receives nothing on macOS, so the state is recorded for
+/// `get_listener_status`, the UI is notified with `input-monitoring-required` and the permission
+/// is polled until it is granted.
+pub(crate) fn spawn_input_listener_when_permitted(app: AppHandle) {
+    if check_input_monitoring().unwrap_or(true) {
+        spawn_input_listener();
+        return;
+    }
+
+    warn!("Input monitoring permission not granted, mouse listener is not started");
+    INPUT_MONITORING_REQUIRED.store(true, Ordering::SeqCst);
+    let _ = app.emit("input-monitoring-required", ());
+
+    std::thread::spawn(move || {
+        while !check_input_monitoring().unwrap_or(true) {
+            std::thread::sleep(INPUT_MONITORING_POLL_INTERVAL);
+        }
+
+        info!("Input monitoring permission granted, starting mouse listener");
+        INPUT_MONITORING_REQUIRED.store(false, Ordering::SeqCst);
+        spawn_input_listener();
+        let _ = app.emit("input-monitoring-granted", ());
+    });
+}
@@ -0,0 +1,67 @@
+use crate::error::AppError;
+use serde::Deserialize;
+use std::sync::Mutex;
+
+// display language of human-readable strings produced by the backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum Locale {
+    #[serde(rename = "en")]
+    En,
+    #[serde(rename = "zh-CN")]
+    ZhCn,
+}
+
+impl Locale {
+    /// Pick the display string for this locale.
+    pub fn pick<'a>(self, en: &'a str, zh_cn: &'a str) -> &'a str {
+        match self {
+            Locale::En => en,
+            Locale::ZhCn => zh_cn,
+        }
+    }
+}
+
+// current application locale, synced from the frontend
+static CURRENT_LOCALE: Mutex<Locale> = Mutex::new(Locale::En);
+
+/// Set the application locale used for backend display strings.
+/// Machine-readable values are never localized.
+#[tauri::command]
+pub fn set_locale(locale: Locale) -> Result<(), AppError> {
+    *CURRENT_LOCALE.lock()? = locale;
+    Ok(())
+}
+
+/// Get the current application locale.
+pub(crate) fn current_locale() -> Locale {
+    CURRENT_LOCALE.lock().map_or(Locale::En, |locale| *locale)
+}
+
+/// Format a localized label followed by the value it describes, e.g. "Invalid regex: (".
+pub(crate) fn labeled(en: &str, zh_cn: &str, value: impl std::fmt::Display) -> String {
+    let locale = current_locale();
+    format!(
+        "{}{}{}",
+        locale.pick(en, zh_cn),
+        locale.pick(": ", "："),
+        value
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labeled_follows_current_locale() {
+        assert_eq!(
+            labeled("Invalid regex", "无效的正则表达式", "("),
+            "Invalid regex: ("
+        );
+
+        set_locale(Locale::ZhCn).unwrap();
+        let message = labeled("Invalid regex", "无效的正则表达式", "(");
+        set_locale(Locale::En).unwrap();
+        assert_eq!(message, "无效的正则表达式：(");
+    }
+}
@@ -0,0 +1,54 @@
+use crate::commands::selection::get_last_selection;
+use crate::error::AppError;
+use chrono::Local;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Append text to today's Markdown note in `note_dir`, returning the note path.
+/// Each entry is headed by the time and the source application and URL if known.
+/// The source is looked up when the text is captured, not when it is appended, so callers pass
+/// it in; without `source_app` the application of the last captured selection is used.
+/// The note file and directory are created as needed.
+#[tauri::command]
+pub fn append_to_note(
+    text: String,
+    note_dir: String,
+    source_app: Option<String>,
+    source_url: Option<String>,
+) -> Result<String, AppError> {
+    if text.trim().is_empty() {
+        return Err("Nothing to append".into());
+    }
+
+    let now = Local::now();
+    let dir = PathBuf::from(note_dir);
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("{}.md", now.format("%Y-%m-%d")));
+
+    // compose entry heading from time and source
+    let source_app = source_app.or_else(|| {
+        get_last_selection()
+            .ok()
+            .flatten()
+            .and_then(|last| last.app_id)
+    });
+    let mut heading = format!("## {}", now.format("%H:%M:%S"));
+    if let Some(app_id) = source_app.filter(|s| !s.is_empty()) {
+        heading.push_str(&format!(" · {}", app_id));
+    }
+    if let Some(url) = source_url.filter(|s| !s.is_empty()) {
+        heading.push_str(&format!(" · <{}>", url));
+    }
+    let entry = format!("{}\n\n{}\n\n", heading, text.trim_end());
+
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+
+    // hold an exclusive lock so concurrent appends are not interleaved
+    file.lock()?;
+    let result = file.write_all(entry.as_bytes());
+    file.unlock()?;
+    result?;
+
+    Ok(path.to_string_lossy().into_owned())
+}
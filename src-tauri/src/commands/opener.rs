@@ -0,0 +1,167 @@
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::AppHandle;
+use tauri_plugin_opener::OpenerExt;
+
+// action taken when opening a selection
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OpenAction {
+    Url,
+    Path,
+    Email,
+}
+
+// search provider for web search
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchProvider {
+    Google,
+    Bing,
+    DuckDuckGo,
+    Custom,
+}
+
+impl SearchProvider {
+    /// Get search URL template with `{query}` placeholder.
+    fn template(self) -> Option<&'static str> {
+        match self {
+            SearchProvider::Google => Some("https://www.google.com/search?q={query}"),
+            SearchProvider::Bing => Some("https://www.bing.com/search?q={query}"),
+            SearchProvider::DuckDuckGo => Some("https://duckduckgo.com/?q={query}"),
+            SearchProvider::Custom => None,
+        }
+    }
+}
+
+/// Open selected text as a URL, an existing file path or an email address.
+/// Files are revealed in the file manager instead of being executed.
+#[tauri::command]
+pub fn open_selection(app: AppHandle, text: String) -> Result<OpenAction, AppError> {
+    let text = text.trim();
+
+    // open URL in default browser
+    if let Some(url) = as_url(text) {
+        app.opener().open_url(url, None::<&str>)?;
+        return Ok(OpenAction::Url);
+    }
+
+    // compose email with default mail client
+    if let Some(email) = as_email(text) {
+        app.opener()
+            .open_url(format!("mailto:{}", email), None::<&str>)?;
+        return Ok(OpenAction::Email);
+    }
+
+    // open directory or reveal file in file manager
+    if let Some(path) = as_path(text) {
+        if path.is_dir() {
+            app.opener()
+                .open_path(path.to_string_lossy(), None::<&str>)?;
+        } else {
+            app.opener().reveal_item_in_dir(&path)?;
+        }
+        return Ok(OpenAction::Path);
+    }
+
+    Err("Selection is not a URL, file path or email address".into())
+}
+
+/// Get URL from text, adding scheme to "www." addresses.
+fn as_url(text: &str) -> Option<String> {
+    if text.is_empty() || text.contains(char::is_whitespace) {
+        return None;
+    }
+
+    let lower = text.to_lowercase();
+    if lower.starts_with("http://") || lower.starts_with("https://") {
+        Some(text.to_string())
+    } else if lower.starts_with("www.") && text.len() > 4 {
+        Some(format!("https://{}", text))
+    } else {
+        None
+    }
+}
+
+/// Get email address from text, stripping an optional "mailto:" prefix.
+fn as_email(text: &str) -> Option<&str> {
+    let email = text
+        .strip_prefix("mailto:")
+        .or_else(|| text.strip_prefix("MAILTO:"))
+        .unwrap_or(text);
+    if email.contains(char::is_whitespace) {
+        return None;
+    }
+
+    let (local, domain) = email.split_once('@')?;
+    let valid_domain = domain.contains('.')
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+        && !domain.contains('@');
+    (!local.is_empty() && valid_domain).then_some(email)
+}
+
+/// Get existing file system path from text, expanding "~" to the home directory.
+fn as_path(text: &str) -> Option<PathBuf> {
+    // strip surrounding quotes copied along with paths
+    let text = text.trim_matches(|c| c == '"' || c == '\'');
+    if text.is_empty() {
+        return None;
+    }
+
+    let path = match text.strip_prefix('~') {
+        Some(rest) => {
+            #[cfg(target_os = "windows")]
+            let home = std::env::var("USERPROFILE").ok()?;
+            #[cfg(not(target_os = "windows"))]
+            let home = std::env::var("HOME").ok()?;
+            PathBuf::from(format!("{}{}", home, rest))
+        }
+        None => PathBuf::from(text),
+    };
+
+    path.exists().then_some(path)
+}
+
+/// Search the query with a web search provider in the default browser.
+/// The custom provider uses `template` with a `{query}` placeholder.
+#[tauri::command]
+pub fn web_search(
+    app: AppHandle,
+    query: String,
+    provider: SearchProvider,
+    template: Option<String>,
+) -> Result<(), AppError> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Err("Search query is empty".into());
+    }
+
+    // get URL template for provider
+    let template = match provider.template() {
+        Some(template) => template.to_string(),
+        None => template
+            .filter(|t| t.contains("{query}"))
+            .ok_or("Custom search template must contain {query}")?,
+    };
+
+    let url = template.replace("{query}", &encode_uri_component(query));
+    app.opener().open_url(url, None::<&str>)?;
+
+    Ok(())
+}
+
+/// Percent-encode text for use in a URL query component.
+fn encode_uri_component(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len() * 3);
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
@@ -0,0 +1,47 @@
+use crate::error::AppError;
+use std::path::Path;
+use tauri::AppHandle;
+use tauri_plugin_dialog::DialogExt;
+
+// UTF-8 byte order mark, lets Excel on Windows detect the encoding
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Save text to a file chosen in a save dialog, returning the chosen path.
+/// Returns `None` if the dialog was cancelled. With `bom`, a UTF-8 byte order mark is
+/// written before the text.
+#[tauri::command]
+pub async fn save_output(
+    app: AppHandle,
+    text: String,
+    suggested_name: String,
+    bom: Option<bool>,
+) -> Result<Option<String>, AppError> {
+    let mut dialog = app.dialog().file().set_file_name(&suggested_name);
+
+    // offer the suggested file extension as the default filter
+    if let Some(ext) = Path::new(&suggested_name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+    {
+        dialog = dialog.add_filter(ext.to_uppercase(), &[ext]);
+    }
+
+    // wait for user to choose a path without blocking the async runtime
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    dialog.save_file(move |file_path| {
+        let _ = tx.send(file_path);
+    });
+    let Some(file_path) = rx.await? else {
+        return Ok(None);
+    };
+    let path = file_path.as_path().ok_or("Invalid save path")?;
+
+    let mut content = Vec::with_capacity(text.len() + UTF8_BOM.len());
+    if bom.unwrap_or(false) {
+        content.extend_from_slice(UTF8_BOM);
+    }
+    content.extend_from_slice(text.as_bytes());
+    std::fs::write(path, content)?;
+
+    Ok(Some(path.to_string_lossy().into_owned()))
+}
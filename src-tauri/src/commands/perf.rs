@@ -0,0 +1,102 @@
+use crate::error::AppError;
+use crate::PERF_SAMPLES;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+// maximum number of latency samples kept per operation
+const MAX_SAMPLES: usize = 1000;
+
+// timed operations
+pub(crate) const NATIVE_SELECTION: &str = "nativeSelection";
+pub(crate) const CLIPBOARD_FALLBACK: &str = "clipboardFallback";
+pub(crate) const CURSOR_LOCATION: &str = "cursorLocation";
+
+// selection captures in total and those served by the native API
+static CAPTURES: AtomicU64 = AtomicU64::new(0);
+static NATIVE_CAPTURES: AtomicU64 = AtomicU64::new(0);
+
+// structure to hold latency samples of an operation
+#[derive(Debug, Default)]
+pub struct PerfSamples {
+    count: u64,
+    durations: VecDeque<f64>,
+}
+
+// structure to hold latency statistics of an operation
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyStats {
+    pub count: u64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+}
+
+// structure to hold performance metrics
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerfMetrics {
+    pub operations: HashMap<String, LatencyStats>,
+    pub native_ratio: Option<f64>,
+}
+
+/// Record elapsed time of an operation started at `start`.
+pub(crate) fn record_latency(operation: &'static str, start: Instant) {
+    let elapsed = start.elapsed().as_secs_f64() * 1000.0;
+    if let Ok(mut samples) = PERF_SAMPLES.lock() {
+        let entry = samples.entry(operation).or_default();
+        entry.count += 1;
+        if entry.durations.len() >= MAX_SAMPLES {
+            entry.durations.pop_front();
+        }
+        entry.durations.push_back(elapsed);
+    }
+}
+
+/// Record a selection capture and whether the native API returned it.
+pub(crate) fn record_capture(native: bool) {
+    CAPTURES.fetch_add(1, Ordering::Relaxed);
+    if native {
+        NATIVE_CAPTURES.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Get selection capture latency metrics.
+#[tauri::command]
+pub fn get_perf_metrics() -> Result<PerfMetrics, AppError> {
+    let samples = PERF_SAMPLES.lock()?;
+
+    let operations = samples
+        .iter()
+        .map(|(operation, entry)| {
+            let mut durations: Vec<f64> = entry.durations.iter().copied().collect();
+            durations.sort_by(f64::total_cmp);
+            let stats = LatencyStats {
+                count: entry.count,
+                p50_ms: percentile(&durations, 0.50),
+                p95_ms: percentile(&durations, 0.95),
+            };
+            (operation.to_string(), stats)
+        })
+        .collect();
+
+    // forced clipboard captures never try the native API, so count captures directly
+    let captures = CAPTURES.load(Ordering::Relaxed);
+    let native = NATIVE_CAPTURES.load(Ordering::Relaxed);
+    let native_ratio = (captures > 0).then(|| native as f64 / captures as f64);
+
+    Ok(PerfMetrics {
+        operations,
+        native_ratio,
+    })
+}
+
+/// Get the nearest-rank percentile of sorted durations.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
@@ -84,3 +84,32 @@ pub fn open_input_monitoring() -> Result<(), AppError> {
         Err("Input monitoring settings are only available on macOS".into())
     }
 }
+
+/// Enable accessibility API for the frontmost application on demand.
+/// Sets `AXEnhancedUserInterface`/`AXManualAccessibility` for Chromium and Electron apps.
+#[tauri::command]
+pub fn enable_accessibility_for_frontmost() -> Result<(), AppError> {
+    #[cfg(target_os = "macos")]
+    {
+        crate::platform::enable_accessibility_for_frontmost()
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        // nothing to enable on non-macOS platforms
+        Ok(())
+    }
+}
+
+/// Set how long the accessibility API enabling is cached per process, in seconds.
+/// A duration of 0 disables the cache so AXAPI is enabled on every attempt.
+#[tauri::command]
+pub fn set_axapi_cache_duration(seconds: u64) -> Result<(), AppError> {
+    #[cfg(target_os = "macos")]
+    crate::platform::set_axapi_cache_duration(seconds);
+
+    #[cfg(not(target_os = "macos"))]
+    let _ = seconds;
+
+    Ok(())
+}
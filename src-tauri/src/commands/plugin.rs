@@ -0,0 +1,189 @@
+use crate::commands::clipboard::get_clipboard_text;
+use crate::commands::executor::{
+    execute_javascript, execute_powershell, execute_python, execute_shell,
+};
+use crate::error::{AppError, ErrorKind};
+use crate::{PLUGINS, SETTINGS_STORE};
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
+
+// plugin directory name under app data directory
+const PLUGINS_DIR: &str = "plugins";
+
+// maximum execution time of a plugin
+const PLUGIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+// maximum number of plugins running at the same time
+const MAX_RUNNING_PLUGINS: usize = 2;
+
+// number of currently running plugins
+static RUNNING_PLUGINS: AtomicUsize = AtomicUsize::new(0);
+
+// script language of a plugin, matching the executor commands
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PluginLanguage {
+    JavaScript,
+    Python,
+    Shell,
+    PowerShell,
+}
+
+// plugin manifest loaded from a JSON file
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginManifest {
+    pub name: String,
+    pub language: PluginLanguage,
+    pub code: String,
+    #[serde(default)]
+    pub shortcut: Option<String>,
+    #[serde(default, skip_deserializing)]
+    pub id: String,
+}
+
+/// List loaded plugins, scanning the plugin directory on first use.
+#[tauri::command]
+pub fn list_plugins(app: AppHandle) -> Result<Vec<PluginManifest>, AppError> {
+    if let Some(plugins) = PLUGINS.lock()?.as_ref() {
+        return Ok(plugins.clone());
+    }
+    reload_plugins(app)
+}
+
+/// Rescan `app_data_dir()/plugins/*.json` and return valid plugin manifests.
+/// Malformed manifests are skipped with a warning.
+#[tauri::command]
+pub fn reload_plugins(app: AppHandle) -> Result<Vec<PluginManifest>, AppError> {
+    let dir = app.path().app_data_dir()?.join(PLUGINS_DIR);
+    let plugins = load_plugins(&dir)?;
+    debug!("Loaded {} plugins from {}", plugins.len(), dir.display());
+
+    *PLUGINS.lock()? = Some(plugins.clone());
+    Ok(plugins)
+}
+
+/// Run a plugin by id with the given selection as input data.
+/// Execution is limited by a timeout and a maximum number of concurrent runs.
+#[tauri::command]
+pub async fn run_plugin(app: AppHandle, id: String, data: String) -> Result<String, AppError> {
+    let plugin = list_plugins(app.clone())?
+        .into_iter()
+        .find(|plugin| plugin.id == id)
+        .ok_or_else(|| {
+            AppError::with_kind(ErrorKind::NotFound, format!("Plugin {} not found", id))
+        })?;
+
+    // limit concurrent plugin executions
+    let _guard = PluginRunGuard::acquire()
+        .ok_or_else(|| format!("Too many plugins running (maximum {})", MAX_RUNNING_PLUGINS))?;
+
+    // prepare input data in the same shape as frontend scripts
+    let data = serde_json::json!({
+        "selection": data,
+        "clipboard": get_clipboard_text().unwrap_or_default(),
+    })
+    .to_string();
+
+    // get runtime paths from settings store
+    let store = app.store(SETTINGS_STORE)?;
+    let get_path = |key: &str| {
+        store
+            .get(key)
+            .and_then(|v| v.as_str().map(str::to_string))
+            .filter(|s| !s.is_empty())
+    };
+
+    debug!("Running plugin {} ({:?})", plugin.id, plugin.language);
+    let code = plugin.code;
+    let execution = async {
+        match plugin.language {
+            PluginLanguage::JavaScript => {
+                execute_javascript(code, data, get_path("nodePath"), get_path("denoPath")).await
+            }
+            PluginLanguage::Python => execute_python(code, data, get_path("pythonPath")).await,
+            PluginLanguage::Shell => execute_shell(code, data).await,
+            PluginLanguage::PowerShell => execute_powershell(code, data).await,
+        }
+    };
+
+    // dropping the execution on timeout kills the plugin process, freeing its run slot
+    tokio::time::timeout(PLUGIN_TIMEOUT, execution)
+        .await
+        .map_err(|_| AppError::with_kind(ErrorKind::Timeout, format!("Plugin {} timed out", id)))?
+}
+
+// guard tracking the number of running plugins
+struct PluginRunGuard;
+
+impl PluginRunGuard {
+    /// Reserve a plugin run slot, returning None if all slots are taken.
+    fn acquire() -> Option<Self> {
+        RUNNING_PLUGINS
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |running| {
+                (running < MAX_RUNNING_PLUGINS).then_some(running + 1)
+            })
+            .ok()
+            .map(|_| PluginRunGuard)
+    }
+}
+
+impl Drop for PluginRunGuard {
+    fn drop(&mut self) {
+        RUNNING_PLUGINS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Load plugin manifests from the given directory.
+fn load_plugins(dir: &Path) -> Result<Vec<PluginManifest>, AppError> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut paths: Vec<_> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+
+    let mut plugins = Vec::new();
+    for path in paths {
+        match read_manifest(&path) {
+            Ok(plugin) => plugins.push(plugin),
+            Err(error) => warn!("Skipping plugin {}: {}", path.display(), error),
+        }
+    }
+    Ok(plugins)
+}
+
+/// Read and validate a single plugin manifest.
+fn read_manifest(path: &Path) -> Result<PluginManifest, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let mut plugin: PluginManifest = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+
+    if plugin.name.trim().is_empty() {
+        return Err("Plugin name is empty".to_string());
+    }
+    if plugin.code.trim().is_empty() {
+        return Err("Plugin code is empty".to_string());
+    }
+    if plugin
+        .shortcut
+        .as_deref()
+        .is_some_and(|s| s.trim().is_empty())
+    {
+        plugin.shortcut = None;
+    }
+
+    // plugin id is the manifest file name without extension
+    plugin.id = path
+        .file_stem()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    Ok(plugin)
+}
@@ -0,0 +1,42 @@
+use crate::error::AppError;
+use base64::prelude::*;
+use image::{DynamicImage, ImageFormat, Luma};
+use qrcode::QrCode;
+use std::io::Cursor;
+
+// maximum payload size in bytes, below the QR code version 40 binary capacity
+const MAX_QR_BYTES: usize = 2048;
+
+// maximum rendered image size in pixels
+const MAX_QR_SIZE: u32 = 2048;
+
+/// Generate a QR code for the given text.
+/// Returns a base64 encoded PNG image of at least `size` pixels wide.
+#[tauri::command]
+pub fn generate_qr(text: String, size: u32) -> Result<String, AppError> {
+    if text.is_empty() {
+        return Err("Cannot generate QR code for empty text".into());
+    }
+    if text.len() > MAX_QR_BYTES {
+        return Err(format!(
+            "Text is too long for a QR code ({} bytes, maximum {})",
+            text.len(),
+            MAX_QR_BYTES
+        )
+        .into());
+    }
+
+    // encode text and render as grayscale image
+    let code =
+        QrCode::new(text.as_bytes()).map_err(|e| format!("Failed to encode QR code: {}", e))?;
+    let size = size.clamp(1, MAX_QR_SIZE);
+    let image = code.render::<Luma<u8>>().min_dimensions(size, size).build();
+
+    // write PNG into memory buffer
+    let mut png = Vec::new();
+    DynamicImage::ImageLuma8(image)
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .map_err(|e| format!("Failed to write QR code image: {}", e))?;
+
+    Ok(BASE64_STANDARD.encode(&png))
+}
@@ -0,0 +1,120 @@
+use crate::commands::shortcut::{pause_shortcut_handling, resume_shortcut_handling};
+use crate::error::AppError;
+use crate::{DND_PAUSED, SETTINGS_STORE};
+use chrono::{Datelike, Local, NaiveTime};
+use log::{info, warn};
+use serde::Deserialize;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_store::StoreExt;
+
+// interval between schedule checks
+const SCHEDULE_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+// whether schedule windows mark active or paused time
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DndMode {
+    #[default]
+    Active,
+    Paused,
+}
+
+// time window on given weekdays (1 = Monday, 7 = Sunday)
+#[derive(Debug, Clone, Deserialize)]
+pub struct DndRule {
+    pub days: Vec<u32>,
+    pub start: String,
+    pub end: String,
+}
+
+// do-not-disturb schedule settings
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DndSchedule {
+    #[serde(default)]
+    pub mode: DndMode,
+    #[serde(default)]
+    pub rules: Vec<DndRule>,
+}
+
+impl DndRule {
+    /// Check if the given weekday and time fall within this rule.
+    /// Windows ending before they start wrap past midnight.
+    fn contains(&self, weekday: u32, time: NaiveTime) -> bool {
+        let (Ok(start), Ok(end)) = (
+            NaiveTime::parse_from_str(&self.start, "%H:%M"),
+            NaiveTime::parse_from_str(&self.end, "%H:%M"),
+        ) else {
+            warn!("Invalid do-not-disturb rule: {}-{}", self.start, self.end);
+            return false;
+        };
+
+        if start <= end {
+            self.days.contains(&weekday) && time >= start && time < end
+        } else {
+            // overnight window belongs to the day it starts
+            let previous = if weekday == 1 { 7 } else { weekday - 1 };
+            (self.days.contains(&weekday) && time >= start)
+                || (self.days.contains(&previous) && time < end)
+        }
+    }
+}
+
+/// Start background task that pauses and resumes shortcut handling on schedule.
+pub fn start_dnd_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(SCHEDULE_CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(error) = apply_dnd_schedule(&app) {
+                warn!("Failed to apply do-not-disturb schedule: {}", error);
+            }
+        }
+    });
+}
+
+/// Pause or resume shortcut handling when crossing a schedule boundary.
+fn apply_dnd_schedule(app: &AppHandle) -> Result<(), AppError> {
+    // get schedule from settings store
+    let schedule: DndSchedule = app
+        .store(SETTINGS_STORE)?
+        .get("dndSchedule")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+
+    // resume if schedule was removed while paused by it
+    if schedule.rules.is_empty() {
+        if DND_PAUSED.swap(false, Ordering::Relaxed) {
+            resume_shortcut_handling(app.clone(), None)?;
+            app.emit("dnd-changed", false)?;
+        }
+        return Ok(());
+    }
+
+    let now = Local::now();
+    let weekday = now.weekday().number_from_monday();
+    let in_window = schedule
+        .rules
+        .iter()
+        .any(|rule| rule.contains(weekday, now.time()));
+    let should_pause = match schedule.mode {
+        DndMode::Active => !in_window,
+        DndMode::Paused => in_window,
+    };
+
+    // only act on boundaries so manual pause/resume is not overridden
+    if DND_PAUSED.swap(should_pause, Ordering::Relaxed) == should_pause {
+        return Ok(());
+    }
+
+    if should_pause {
+        pause_shortcut_handling(app.clone(), None)?;
+    } else {
+        resume_shortcut_handling(app.clone(), None)?;
+    }
+    info!("Do-not-disturb schedule changed, paused: {}", should_pause);
+    app.emit("dnd-changed", should_pause)?;
+
+    Ok(())
+}
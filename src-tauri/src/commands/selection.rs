@@ -1,39 +1,547 @@
-use crate::commands::clipboard::{clear_clipboard, get_clipboard_text, with_clipboard_backup};
-use crate::commands::keyboard::send_copy_keys;
+use crate::commands::clipboard::{
+    clear_clipboard, get_clipboard_text, set_clipboard_text, with_clipboard_backup,
+};
+use crate::commands::identifier::{
+    get_copy_keys_override, get_selection_method_override, is_blocked, is_force_fallback_app,
+    matches_wildcard,
+};
+use crate::commands::keyboard::{send_copy_keys, send_shortcut_keys};
+use crate::commands::locale::{current_locale, Locale};
+use crate::commands::perf::{record_capture, record_latency, CLIPBOARD_FALLBACK, NATIVE_SELECTION};
+use crate::commands::permission::check_accessibility;
 use crate::commands::shortcut::ShortcutHandlerGuard;
-use crate::error::AppError;
+use crate::commands::text::sanitize_text;
+use crate::error::{AppError, ErrorKind};
 use crate::platform;
 use log::warn;
+use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::time::Duration;
-use tauri::AppHandle;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_store::StoreExt;
+use unicode_segmentation::UnicodeSegmentation;
 
-use crate::SELECTION_TEXT_CACHE;
+use crate::{LAST_SELECTION, LAST_SELECTION_EXPIRY, SELECTION_TEXT_CACHE, SETTINGS_STORE};
+
+// structure to hold the last successfully captured selection
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LastSelection {
+    pub text: String,
+    pub app_id: Option<String>,
+    pub captured_at: u64,
+}
+
+// structure to hold a captured selection and whether it was cut to `maxSelectionChars`,
+// truncated text ends with a marker that is not counted in `dropped_chars`
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CapturedSelection {
+    pub text: String,
+    pub truncated: bool,
+    pub dropped_chars: usize,
+}
+
+// selection range within the focused text, in characters
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct SelectionRange {
+    pub location: usize,
+    pub length: usize,
+}
+
+// text unit to expand a selection to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SelectionUnit {
+    Word,
+    Sentence,
+    Paragraph,
+}
+
+// method used to capture selected text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SelectionMethod {
+    Native,
+    Clipboard,
+    Auto,
+}
+
+// structure to hold the outcome of comparing capture methods for an application
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalibrationResult {
+    pub recommended: SelectionMethod,
+    pub native_ms: u64,
+    pub fallback_ms: u64,
+    pub native_matches_fallback: bool,
+}
 
 // maximum wait time in milliseconds for clipboard to update
 static MAX_WAIT_TIME: AtomicU64 = AtomicU64::new(1000);
 
-/// Get selected text.
+// default maximum number of characters kept from a captured selection
+const DEFAULT_MAX_SELECTION_CHARS: usize = 100_000;
+
+// shortcut reported by on-demand captures, same as a drag-end gesture
+const CAPTURE_SHORTCUT: &str = "MouseClick+MouseMove";
+
+/// Get selected text, reporting whether it was truncated to `maxSelectionChars`.
+/// The capture method follows per-app overrides in `selectionMethodOverrides`.
+/// When `keep_on_clipboard` is true, the captured text is left on the clipboard.
+#[tauri::command]
+pub async fn get_selection(
+    app: AppHandle,
+    mouse: Option<bool>,
+    keep_on_clipboard: Option<bool>,
+) -> Result<CapturedSelection, AppError> {
+    // skip native selection for apps where it returns wrong text
+    let method = if is_force_fallback_app(&app) {
+        SelectionMethod::Clipboard
+    } else {
+        // use per-app method override if configured
+        get_selection_method_override(&app).unwrap_or(SelectionMethod::Auto)
+    };
+    capture_selection(&app, mouse, keep_on_clipboard, method).await
+}
+
+/// Get selected text using the given capture method.
 #[tauri::command]
-pub async fn get_selection(app: AppHandle, mouse: Option<bool>) -> Result<String, AppError> {
+pub async fn get_selection_with_method(
+    app: AppHandle,
+    method: SelectionMethod,
+    mouse: Option<bool>,
+    keep_on_clipboard: Option<bool>,
+) -> Result<CapturedSelection, AppError> {
+    capture_selection(&app, mouse, keep_on_clipboard, method).await
+}
+
+/// Capture selected text, then truncate and remember it.
+async fn capture_selection(
+    app: &AppHandle,
+    mouse: Option<bool>,
+    keep_on_clipboard: Option<bool>,
+    method: SelectionMethod,
+) -> Result<CapturedSelection, AppError> {
     // suspend shortcut handling to avoid interference
     let _guard = ShortcutHandlerGuard::suspend();
 
+    let text = match read_selection(app, mouse.unwrap_or(false), method).await {
+        Ok(text) => text,
+        Err(error) => {
+            // let the main window explain failures such as missing permissions
+            let _ = app.emit("selection-error", &error);
+            return Err(error);
+        }
+    };
+
+    // put captured text on clipboard instead of keeping the restored contents
+    if keep_on_clipboard.unwrap_or(false) && !text.is_empty() {
+        set_clipboard_text(text.clone())?;
+    }
+
+    let selection = truncate_selection(app, sanitize_selection(app, text));
+    remember_selection(&selection.text);
+    Ok(selection)
+}
+
+/// Read selected text without truncation, natively or through clipboard.
+/// Callers are responsible for suspending shortcut handling.
+pub(crate) async fn read_selection(
+    app: &AppHandle,
+    mouse: bool,
+    method: SelectionMethod,
+) -> Result<String, AppError> {
+    if method == SelectionMethod::Clipboard {
+        record_capture(false);
+        return read_selection_fallback(app, mouse).await;
+    }
+
+    // report missing permission instead of quietly using the clipboard
+    if is_accessibility_required(app)? {
+        return Err(AppError::with_kind(
+            ErrorKind::PermissionDenied,
+            "Accessibility permission not granted",
+        ));
+    }
+
     // try using platform native API to get selected text first
-    if let Ok(text) = platform::get_selection() {
-        if !text.is_empty() {
+    let start = Instant::now();
+    let native = platform::get_selection();
+    record_latency(NATIVE_SELECTION, start);
+
+    let succeeded = native.as_ref().is_ok_and(|text| !text.is_empty());
+    record_capture(succeeded);
+
+    match native {
+        Ok(text) if !text.is_empty() => {
             // clear cache to avoid stale data
             if let Ok(mut cache) = SELECTION_TEXT_CACHE.lock() {
                 *cache = None;
             }
 
-            return Ok(text);
+            Ok(text)
+        }
+        _ => {
+            // native-only capture never touches the clipboard
+            if method == SelectionMethod::Native {
+                return native;
+            }
+
+            // if native API fails, fall back to clipboard method
+            warn!("Failed to get selection natively, fallback to clipboard method");
+            read_selection_fallback(app, mouse).await
+        }
+    }
+}
+
+/// Check if native capture must stop because accessibility permission is missing.
+/// Users can opt back into silent clipboard fallback with `accessibilityFallback`.
+fn is_accessibility_required(app: &AppHandle) -> Result<bool, AppError> {
+    if check_accessibility()? {
+        return Ok(false);
+    }
+
+    let fallback = app
+        .store(SETTINGS_STORE)?
+        .get("accessibilityFallback")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    Ok(!fallback)
+}
+
+/// Read selected text through clipboard and record its latency.
+async fn read_selection_fallback(app: &AppHandle, mouse: bool) -> Result<String, AppError> {
+    let start = Instant::now();
+    let result = get_selection_fallback(app.clone(), mouse).await;
+    record_latency(CLIPBOARD_FALLBACK, start);
+    result
+}
+
+/// Capture the current selection on demand and emit it like a drag-end gesture.
+/// Intended to be bound to a global shortcut for keyboard-only users.
+#[tauri::command]
+pub async fn capture_selection_now(app: AppHandle) -> Result<(), AppError> {
+    // check if current frontmost application/website is in blacklist
+    if is_blocked(app.clone())? {
+        return Ok(());
+    }
+
+    let selection = get_selection(app.clone(), Some(false), None).await?;
+    if !selection.text.trim().is_empty() {
+        // emit event if selection is not empty
+        let event_data = serde_json::json!({
+            "shortcut": CAPTURE_SHORTCUT,
+            "selection": selection.text,
+            "truncated": selection.truncated
+        });
+        app.emit("shortcut", event_data)?;
+    }
+
+    Ok(())
+}
+
+/// Capture the current selection with both the native and clipboard methods, compare
+/// their timing and text, and save the recommended method for `app_glob` in
+/// `selectionMethodOverrides`. The frontmost application must match `app_glob`.
+#[tauri::command]
+pub async fn calibrate_selection(
+    app: AppHandle,
+    app_glob: String,
+) -> Result<CalibrationResult, AppError> {
+    let app_glob = app_glob.trim().to_string();
+    if app_glob.is_empty() {
+        return Err("Application pattern must not be empty".into());
+    }
+    let app_id = platform::get_frontmost_app_id().ok_or("Failed to get frontmost application")?;
+    if !matches_wildcard(&app_glob, &app_id) {
+        return Err(format!(
+            "Frontmost application {} does not match {}",
+            app_id, app_glob
+        )
+        .into());
+    }
+
+    // suspend shortcut handling to avoid interference
+    let _guard = ShortcutHandlerGuard::suspend();
+
+    // capture with platform native API
+    let start = Instant::now();
+    let native = platform::get_selection().unwrap_or_default();
+    let native_ms = start.elapsed().as_millis() as u64;
+
+    // capture through clipboard
+    let start = Instant::now();
+    let fallback = get_selection_fallback(app.clone(), false).await?;
+    let fallback_ms = start.elapsed().as_millis() as u64;
+
+    if native.trim().is_empty() && fallback.trim().is_empty() {
+        return Err(AppError::with_kind(
+            ErrorKind::NotFound,
+            "No selected text found",
+        ));
+    }
+
+    // clipboard copies may differ in trailing line breaks only
+    let native_matches_fallback = native.trim() == fallback.trim();
+    let recommended = if native_matches_fallback || fallback.trim().is_empty() {
+        SelectionMethod::Native
+    } else {
+        SelectionMethod::Clipboard
+    };
+
+    // save recommendation to per-app overrides
+    let store = app.store(SETTINGS_STORE)?;
+    let mut overrides = store
+        .get("selectionMethodOverrides")
+        .and_then(|v| v.as_object().cloned())
+        .unwrap_or_default();
+    overrides.insert(app_glob, serde_json::to_value(recommended)?);
+    store.set("selectionMethodOverrides", overrides);
+    store.save()?;
+
+    Ok(CalibrationResult {
+        recommended,
+        native_ms,
+        fallback_ms,
+        native_matches_fallback,
+    })
+}
+
+/// Get the selection range within the focused text.
+#[tauri::command]
+pub fn get_selection_range() -> Result<SelectionRange, AppError> {
+    let (location, length) = platform::get_selection_range()?;
+    Ok(SelectionRange { location, length })
+}
+
+/// Select a range within the focused text, e.g. to expand a selection to a sentence.
+#[tauri::command]
+pub fn set_selection_range(location: usize, length: usize) -> Result<(), AppError> {
+    platform::set_selection_range(location, length)
+}
+
+/// Dump a compact tree of the focused element and its descendants for bug reports,
+/// showing where selectable text lives in applications whose selection cannot be read.
+#[tauri::command]
+pub fn dump_focused_tree(max_nodes: usize) -> Result<String, AppError> {
+    platform::dump_focused_tree(max_nodes)
+}
+
+/// Expand the selection to the enclosing word, sentence or paragraph.
+/// If the selection already covers whole units, the next unit is included.
+#[tauri::command]
+pub fn expand_selection(unit: SelectionUnit) -> Result<SelectionRange, AppError> {
+    let text = platform::get_focused_text()?;
+    let (location, length) = platform::get_selection_range()?;
+
+    // convert UTF-16 offsets used by accessibility APIs to byte offsets
+    let start = utf16_to_byte_offset(&text, location);
+    let end = utf16_to_byte_offset(&text, location + length);
+
+    let bounds = unit_boundaries(&text, unit);
+    let new_start = bounds
+        .iter()
+        .rev()
+        .find(|&&b| b <= start)
+        .copied()
+        .unwrap_or(0);
+
+    // leave trailing whitespace of sentences and paragraphs unselected
+    let trim = |bound: usize| match unit {
+        SelectionUnit::Word => bound,
+        _ => new_start + text[new_start..bound].trim_end().len(),
+    };
+
+    let mut new_end = bounds
+        .iter()
+        .find(|&&b| b >= end)
+        .map(|&b| trim(b).max(end))
+        .unwrap_or(text.len());
+    if new_start == start && new_end == end {
+        // selection already covers whole units, include the next one
+        new_end = bounds
+            .iter()
+            .filter(|&&b| b > end)
+            .map(|&b| trim(b))
+            .find(|&e| e > end)
+            .unwrap_or(end);
+    }
+
+    let range = SelectionRange {
+        location: text[..new_start].encode_utf16().count(),
+        length: text[new_start..new_end].encode_utf16().count(),
+    };
+    platform::set_selection_range(range.location, range.length)?;
+    Ok(range)
+}
+
+/// Collect byte offsets of unit boundaries in text, including both ends.
+fn unit_boundaries(text: &str, unit: SelectionUnit) -> Vec<usize> {
+    let mut bounds: Vec<usize> = match unit {
+        SelectionUnit::Word => text.split_word_bound_indices().map(|(i, _)| i).collect(),
+        SelectionUnit::Sentence => text
+            .split_sentence_bound_indices()
+            .map(|(i, _)| i)
+            .collect(),
+        SelectionUnit::Paragraph => std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect(),
+    };
+    bounds.push(text.len());
+    bounds
+}
+
+/// Convert an offset in UTF-16 code units to a byte offset in text.
+fn utf16_to_byte_offset(text: &str, offset: usize) -> usize {
+    let mut units = 0;
+    for (index, c) in text.char_indices() {
+        if units >= offset {
+            return index;
+        }
+        units += c.len_utf16();
+    }
+    text.len()
+}
+
+/// Get the last successfully captured selection if it is not stale.
+#[tauri::command]
+pub fn get_last_selection() -> Result<Option<LastSelection>, AppError> {
+    let mut last = LAST_SELECTION.lock()?;
+
+    // clear last selection if it has expired (0 means never expire)
+    let expiry = LAST_SELECTION_EXPIRY.load(Ordering::Relaxed);
+    if let Some((_, recorded_at)) = last.as_ref() {
+        if expiry > 0 && recorded_at.elapsed() >= Duration::from_secs(expiry) {
+            *last = None;
+        }
+    }
+
+    Ok(last.as_ref().map(|(selection, _)| selection.clone()))
+}
+
+/// Set the staleness threshold in seconds for the last captured selection.
+#[tauri::command]
+pub fn set_last_selection_expiry(seconds: u64) -> Result<(), AppError> {
+    LAST_SELECTION_EXPIRY.store(seconds, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Record the captured selection with its application context.
+/// Empty selections clear the record.
+fn remember_selection(text: &str) {
+    if let Ok(mut last) = LAST_SELECTION.lock() {
+        if text.trim().is_empty() {
+            *last = None;
+            return;
         }
+
+        let captured_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default();
+        *last = Some((
+            LastSelection {
+                text: text.to_string(),
+                app_id: platform::get_frontmost_app_id(),
+                captured_at,
+            },
+            Instant::now(),
+        ));
     }
+}
 
-    // if native API fails, fall back to clipboard method
-    warn!("Failed to get selection natively, fallback to clipboard method");
-    get_selection_fallback(app, mouse.unwrap_or(false)).await
+/// Remove invisible characters from captured text if `sanitizeSelection` is enabled.
+fn sanitize_selection(app: &AppHandle, text: String) -> String {
+    let enabled = app
+        .store(SETTINGS_STORE)
+        .ok()
+        .and_then(|store| store.get("sanitizeSelection"))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    if enabled {
+        sanitize_text(text, None)
+    } else {
+        text
+    }
+}
+
+/// Truncate selected text to the configured maximum length.
+/// A localized marker with the number of dropped characters is appended to truncated text,
+/// so the cut stays visible wherever the text ends up.
+fn truncate_selection(app: &AppHandle, text: String) -> CapturedSelection {
+    // get maximum selection length from settings store (0 means unlimited)
+    let max_chars = app
+        .store(SETTINGS_STORE)
+        .ok()
+        .and_then(|store| store.get("maxSelectionChars"))
+        .and_then(|v| v.as_u64())
+        .map(|v| v as usize)
+        .unwrap_or(DEFAULT_MAX_SELECTION_CHARS);
+
+    // find byte offset of the character right after the cap
+    let offset = match max_chars {
+        0 => None,
+        _ => text.char_indices().nth(max_chars).map(|(offset, _)| offset),
+    };
+    let Some(offset) = offset else {
+        return CapturedSelection {
+            text,
+            ..Default::default()
+        };
+    };
+
+    let dropped_chars = text[offset..].chars().count();
+    warn!(
+        "Selection exceeds {} characters, truncated {} characters",
+        max_chars, dropped_chars
+    );
+
+    let mut text = text;
+    text.truncate(offset);
+    text.push_str(&truncation_marker(dropped_chars));
+    CapturedSelection {
+        text,
+        truncated: true,
+        dropped_chars,
+    }
+}
+
+/// Format the marker appended to truncated text, e.g. `…[truncated 42 chars]`.
+fn truncation_marker(dropped_chars: usize) -> String {
+    match current_locale() {
+        Locale::En => format!("…[truncated {} chars]", dropped_chars),
+        Locale::ZhCn => format!("…[已截断 {} 个字符]", dropped_chars),
+    }
+}
+
+/// Poll the clipboard until it has text or the wait time is exceeded.
+fn wait_for_clipboard_text(max_wait_time: Duration) -> String {
+    let check_interval = Duration::from_millis(5); // check interval 5ms
+    let max_attempts = max_wait_time.as_millis() / check_interval.as_millis();
+
+    for _attempt in 0..max_attempts {
+        std::thread::sleep(check_interval);
+
+        // read current clipboard text
+        if let Ok(current_text) = get_clipboard_text() {
+            if !current_text.is_empty() {
+                // if clipboard content changed, copy operation completed
+                return current_text;
+            }
+        }
+    }
+
+    String::new()
+}
+
+/// Check if thread priority should be raised while polling the clipboard.
+fn is_priority_boost_enabled(app: &AppHandle) -> bool {
+    app.store(SETTINGS_STORE)
+        .ok()
+        .and_then(|store| store.get("boostCapturePriority"))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
 }
 
 /// Get selected text through clipboard.
@@ -49,30 +557,32 @@ async fn get_selection_fallback(app: AppHandle, mouse: bool) -> Result<String, A
 
         // send copy shortcut
         // https://github.com/enigo-rs/enigo/issues/153
+        let copy_keys = get_copy_keys_override(&app);
         let _ = app.run_on_main_thread(move || {
             // mouse-triggered selections don't need to release modifier keys
-            let _ = send_copy_keys(Some(false), Some(!mouse));
+            // use per-app copy shortcut if configured, e.g. Control+Shift+KeyC in terminals
+            match copy_keys {
+                Some(keys) if send_shortcut_keys(&keys, !mouse).is_ok() => {}
+                _ => {
+                    let _ = send_copy_keys(Some(false), Some(!mouse));
+                }
+            }
         });
 
-        // wait for clipboard content to change in a loop
+        // wait for clipboard content to change on a blocking thread,
+        // optionally with raised priority so polling isn't starved on busy systems
         let max_wait_time = Duration::from_millis(MAX_WAIT_TIME.load(Ordering::Relaxed));
-        let check_interval = Duration::from_millis(5); // check interval 5ms
-        let max_attempts = max_wait_time.as_millis() / check_interval.as_millis();
-
-        let mut selected_text = String::new();
-
-        for _attempt in 0..max_attempts {
-            tokio::time::sleep(check_interval).await;
-
-            // read current clipboard text
-            if let Ok(current_text) = get_clipboard_text() {
-                if !current_text.is_empty() {
-                    // if clipboard content changed, copy operation completed
-                    selected_text = current_text;
-                    break;
-                }
-            }
-        }
+        let boost = is_priority_boost_enabled(&app);
+        let selected_text = tokio::task::spawn_blocking(move || {
+            let _boost = if boost {
+                platform::boost_thread_priority()
+            } else {
+                None
+            };
+            wait_for_clipboard_text(max_wait_time)
+        })
+        .await
+        .map_err(|e| e.to_string())?;
 
         if selected_text.is_empty() {
             warn!(
@@ -0,0 +1,125 @@
+use crate::commands::selection::{read_selection, SelectionMethod};
+use crate::commands::shortcut::{mark_synthetic_input, ShortcutHandlerGuard};
+use crate::commands::typer::{enter_text, EnterTextOptions};
+use crate::commands::window::{hide_window, show_window};
+use crate::error::AppError;
+use crate::{platform, ENIGO};
+use enigo::{Direction, Key, Keyboard};
+use log::warn;
+use serde::Serialize;
+use std::time::Duration;
+use tauri::AppHandle;
+
+// label of the window hosting the throwaway text field
+const SELFTEST_WINDOW: &str = "selftest";
+
+// delay for the self-test window to take focus before pasting into it
+const SELFTEST_FOCUS_DELAY: Duration = Duration::from_millis(300);
+
+// structure to describe the outcome of a round trip self-test
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelftestResult {
+    pub captured_ok: bool,
+    pub inserted_ok: bool,
+}
+
+/// Run a full "insert → capture" round trip: the test text is pasted and selected by
+/// `enter_text`, then read back with the same capture path as `get_selection`.
+/// By default the text goes into a throwaway field in a hidden app-owned window.
+/// In dry-run mode the user's focused field is used instead, and the text is removed again.
+#[tauri::command]
+pub async fn selftest_roundtrip(
+    app: AppHandle,
+    test_text: String,
+    dry_run: Option<bool>,
+) -> Result<SelftestResult, AppError> {
+    if test_text.is_empty() {
+        return Err("Test text must not be empty".into());
+    }
+
+    if dry_run.unwrap_or(false) {
+        let result = roundtrip(&app, &test_text).await?;
+        remove_test_text(&app, &test_text, &result).await;
+        return Ok(result);
+    }
+
+    // show the throwaway field, remembering where the user was
+    if let Err(e) = platform::remember_focus() {
+        warn!("Failed to remember focus: {}", e);
+    }
+    show_window(&app, SELFTEST_WINDOW).ok_or("Self-test window not found")?;
+    tokio::time::sleep(SELFTEST_FOCUS_DELAY).await;
+
+    let result = roundtrip(&app, &test_text).await;
+
+    // hide the throwaway field and return focus to the user
+    hide_window(&app, SELFTEST_WINDOW);
+    let _ = app.run_on_main_thread(|| {
+        if let Err(e) = platform::restore_focus() {
+            warn!("Failed to restore focus: {}", e);
+        }
+    });
+
+    result
+}
+
+/// Paste test text into the focused field and read it back.
+async fn roundtrip(app: &AppHandle, test_text: &str) -> Result<SelftestResult, AppError> {
+    // paste test text into the focused field and select it
+    let options = EnterTextOptions {
+        verify: true,
+        ..Default::default()
+    };
+    let result = enter_text(app.clone(), test_text.to_string(), None, Some(options)).await?;
+
+    // read the selected text back
+    let captured_ok = {
+        let _guard = ShortcutHandlerGuard::suspend();
+        read_selection(app, false, SelectionMethod::Auto)
+            .await
+            .is_ok_and(|text| text == test_text)
+    };
+
+    Ok(SelftestResult {
+        captured_ok,
+        // fall back to the read back result when the text length cannot be checked
+        inserted_ok: result.inserted.unwrap_or(captured_ok),
+    })
+}
+
+/// Remove test text pasted into the user's field.
+/// The text is only deleted once it is confirmed to be the selection, so nothing else is lost.
+async fn remove_test_text(app: &AppHandle, test_text: &str, result: &SelftestResult) {
+    if !result.inserted_ok {
+        return;
+    }
+
+    let _guard = ShortcutHandlerGuard::suspend();
+    let selected = result.captured_ok
+        || (platform::select_backward_range(0, test_text.chars().count()).is_ok()
+            && read_selection(app, false, SelectionMethod::Auto)
+                .await
+                .is_ok_and(|text| text == test_text));
+    if !selected {
+        warn!("Failed to select test text, it was left in the focused field");
+        return;
+    }
+
+    // first try using native API to delete selection, then fall back to backspace
+    if platform::replace_selection("").is_err() {
+        if let Err(e) = press_backspace() {
+            warn!("Failed to remove test text: {}", e);
+        }
+    }
+}
+
+/// Press backspace to delete the selection.
+fn press_backspace() -> Result<(), AppError> {
+    mark_synthetic_input();
+    ENIGO
+        .lock()?
+        .as_mut()?
+        .key(Key::Backspace, Direction::Click)?;
+    Ok(())
+}
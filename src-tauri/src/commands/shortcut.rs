@@ -1,12 +1,17 @@
 use crate::error::AppError;
 use crate::{
-    IBEAM_CURSOR, LONG_PRESS, LONG_PRESS_DURATION, REGISTERED_SHORTCUTS, SHORTCUT_PAUSED,
-    SHORTCUT_SUSPEND,
+    CAPTURE_HOTKEY, DISMISS_KEY, IBEAM_CURSOR, LONG_PRESS, LONG_PRESS_DURATION,
+    REGISTERED_SHORTCUTS, SHORTCUT_PAUSED, SHORTCUT_SUSPEND, SUPPRESS_NEXT_CAPTURE,
+    SYNTHETIC_INPUT_UNTIL,
 };
 use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
 use tauri::AppHandle;
 use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, Shortcut};
 
+// grace period after injecting keys during which input events may still be our own
+const SYNTHETIC_INPUT_GRACE: Duration = Duration::from_millis(300);
+
 // guard to suspend shortcut event handling within a scope
 pub struct ShortcutHandlerGuard;
 
@@ -23,6 +28,33 @@ impl Drop for ShortcutHandlerGuard {
     }
 }
 
+/// Mark input events within the grace period as our own, called where keys are injected.
+/// Injected keys reach the input listener asynchronously, possibly after the shortcut
+/// handler guard is dropped.
+pub(crate) fn mark_synthetic_input() {
+    if let Ok(mut until) = SYNTHETIC_INPUT_UNTIL.lock() {
+        *until = Some(Instant::now() + SYNTHETIC_INPUT_GRACE);
+    }
+}
+
+/// Check if input events are likely our own recently injected keystrokes.
+pub(crate) fn is_synthetic_input() -> bool {
+    SYNTHETIC_INPUT_UNTIL
+        .lock()
+        .is_ok_and(|until| until.is_some_and(|until| Instant::now() < until))
+}
+
+/// Skip the next selection capture, e.g. around a programmatic copy.
+#[tauri::command]
+pub fn suppress_next_capture() {
+    SUPPRESS_NEXT_CAPTURE.store(true, Ordering::Relaxed);
+}
+
+/// Consume the one-shot flag set by `suppress_next_capture`.
+pub(crate) fn take_suppressed_capture() -> bool {
+    SUPPRESS_NEXT_CAPTURE.swap(false, Ordering::Relaxed)
+}
+
 /// Pause shortcut event handling by unregistering all shortcuts.
 #[tauri::command]
 pub fn pause_shortcut_handling(
@@ -95,6 +127,9 @@ pub fn register_shortcut(app: AppHandle, shortcut: String) -> Result<(), AppErro
 
     // parse and create shortcut object
     let hotkey = parse_shortcut(&shortcut)?;
+    if is_capture_hotkey(&hotkey) {
+        return Err(format!("Shortcut {} is already used to capture selection", shortcut).into());
+    }
 
     // use plugin to register shortcut
     app.global_shortcut().register(hotkey)?;
@@ -142,6 +177,56 @@ pub fn is_shortcut_registered(shortcut: String) -> Result<bool, AppError> {
     Ok(is_registered)
 }
 
+/// Set the global hotkey that captures the selection, or clear it with None.
+/// Unlike mouse gestures, which need input monitoring permission on macOS, the hotkey is
+/// registered through the global shortcut plugin and only needs accessibility permission.
+#[tauri::command]
+pub fn set_capture_hotkey(app: AppHandle, shortcut: Option<String>) -> Result<(), AppError> {
+    let hotkey = shortcut
+        .as_deref()
+        .filter(|s| !s.is_empty())
+        .map(parse_shortcut)
+        .transpose()?;
+
+    // reject a hotkey that is already bound to a rule
+    if let (Some(hotkey), Some(shortcut)) = (hotkey, &shortcut) {
+        if REGISTERED_SHORTCUTS.lock()?.contains_key(&hotkey.id) {
+            return Err(format!("Shortcut {} is already registered", shortcut).into());
+        }
+    }
+
+    let mut capture = CAPTURE_HOTKEY.lock()?;
+    if let Some(previous) = capture.take() {
+        app.global_shortcut().unregister(previous).ok();
+    }
+    if let Some(hotkey) = hotkey {
+        app.global_shortcut().register(hotkey)?;
+        *capture = Some(hotkey);
+    }
+
+    Ok(())
+}
+
+/// Re-register the capture hotkey, e.g. after the system wakes from sleep.
+/// Returns whether a capture hotkey is set.
+pub(crate) fn rearm_capture_hotkey(app: &AppHandle) -> Result<bool, AppError> {
+    let capture = CAPTURE_HOTKEY.lock()?;
+    let Some(hotkey) = *capture else {
+        return Ok(false);
+    };
+
+    app.global_shortcut().unregister(hotkey).ok();
+    app.global_shortcut().register(hotkey)?;
+    Ok(true)
+}
+
+/// Check if a hotkey is the capture hotkey set by `set_capture_hotkey`.
+pub(crate) fn is_capture_hotkey(hotkey: &Shortcut) -> bool {
+    CAPTURE_HOTKEY
+        .lock()
+        .is_ok_and(|capture| capture.is_some_and(|capture| capture.id == hotkey.id))
+}
+
 /// Set the long press enabled state.
 #[tauri::command]
 pub fn set_long_press_enabled(enabled: bool) -> Result<(), AppError> {
@@ -163,11 +248,30 @@ pub fn set_ibeam_cursor_enabled(enabled: bool) -> Result<(), AppError> {
     Ok(())
 }
 
+/// Set the key that dismisses the popup and toolbar, such as `Escape` (empty to disable).
+#[tauri::command]
+pub fn set_dismiss_key(key: String) -> Result<(), AppError> {
+    *DISMISS_KEY.lock()? = key;
+    Ok(())
+}
+
+/// Set additional I-beam cursor hotSpots to recognize (macOS only).
+#[tauri::command]
+pub fn set_ibeam_hotspots(hotspots: Vec<(f64, f64)>) -> Result<(), AppError> {
+    #[cfg(target_os = "macos")]
+    crate::platform::set_ibeam_hotspots(hotspots)?;
+
+    #[cfg(not(target_os = "macos"))]
+    let _ = hotspots;
+
+    Ok(())
+}
+
 /// Parse a shortcut string and create a Shortcut object.
 /// Supported formats:
 /// - "Meta+A", "Control+A", "Alt+A", "Shift+A"
 /// - "Control+Shift+A", "Meta+Alt+A", etc.
-fn parse_shortcut(shortcut: &str) -> Result<Shortcut, AppError> {
+pub(crate) fn parse_shortcut(shortcut: &str) -> Result<Shortcut, AppError> {
     // split by '+'
     let keys: Vec<&str> = shortcut.split('+').collect();
     if keys.is_empty() {
@@ -0,0 +1,1873 @@
+use crate::commands::locale::{current_locale, labeled};
+use crate::error::AppError;
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, FixedOffset, Local, NaiveDateTime, TimeZone, Utc};
+use pinyin::ToPinyin;
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+use regex::RegexBuilder;
+use serde::{Deserialize, Serialize};
+use similar::{ChangeTag, TextDiff};
+use std::cmp::Ordering;
+use std::collections::hash_map::RandomState;
+use std::collections::HashSet;
+use std::hash::{BuildHasher, Hasher};
+use unicode_segmentation::UnicodeSegmentation;
+use zhconv::{zhconv, Variant};
+
+// offset between full-width forms (U+FF01..U+FF5E) and ASCII (U+0021..U+007E)
+const FULL_WIDTH_OFFSET: u32 = 0xFEE0;
+
+// full-width symbols outside the contiguous ASCII block and their half-width forms
+const WIDTH_SYMBOLS: &[(char, char)] = &[
+    ('\u{3000}', ' '),
+    ('\u{FFE0}', '\u{00A2}'),
+    ('\u{FFE1}', '\u{00A3}'),
+    ('\u{FFE2}', '\u{00AC}'),
+    ('\u{FFE3}', '\u{00AF}'),
+    ('\u{FFE4}', '\u{00A6}'),
+    ('\u{FFE5}', '\u{00A5}'),
+    ('\u{FFE6}', '\u{20A9}'),
+];
+
+// target character width
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Width {
+    Half,
+    Full,
+}
+
+/// Convert text between full-width and half-width forms.
+#[tauri::command]
+pub fn convert_width(text: String, to: Width) -> Result<String, AppError> {
+    Ok(match to {
+        Width::Half => text.chars().map(to_half_width).collect(),
+        Width::Full => text.chars().map(to_full_width).collect(),
+    })
+}
+
+/// Convert a full-width character to its half-width form.
+fn to_half_width(c: char) -> char {
+    if let Some(&(_, half)) = WIDTH_SYMBOLS.iter().find(|(full, _)| *full == c) {
+        return half;
+    }
+    match c {
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - FULL_WIDTH_OFFSET).unwrap_or(c),
+        _ => c,
+    }
+}
+
+/// Convert a half-width character to its full-width form.
+fn to_full_width(c: char) -> char {
+    if let Some(&(full, _)) = WIDTH_SYMBOLS.iter().find(|(_, half)| *half == c) {
+        return full;
+    }
+    match c {
+        '\u{0021}'..='\u{007E}' => char::from_u32(c as u32 + FULL_WIDTH_OFFSET).unwrap_or(c),
+        _ => c,
+    }
+}
+
+// target Chinese script variant
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChineseVariant {
+    Simplified,
+    Traditional,
+    Mainland,
+    Taiwan,
+    HongKong,
+}
+
+/// Convert text between Simplified and Traditional Chinese.
+/// Conversion is phrase-based, and regional targets also convert vocabulary
+/// (e.g. "软件" becomes "軟體" for Taiwan but "軟件" for Hong Kong, and "軟體" becomes
+/// "软件" for Mainland China).
+#[tauri::command]
+pub fn convert_chinese(text: String, to: ChineseVariant) -> Result<String, AppError> {
+    let variant = match to {
+        ChineseVariant::Simplified => Variant::ZhHans,
+        ChineseVariant::Traditional => Variant::ZhHant,
+        ChineseVariant::Mainland => Variant::ZhCN,
+        ChineseVariant::Taiwan => Variant::ZhTW,
+        ChineseVariant::HongKong => Variant::ZhHK,
+    };
+    Ok(zhconv(&text, variant))
+}
+
+// pinyin tone notation
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ToneStyle {
+    Marks,
+    Numbers,
+    None,
+}
+
+/// Annotate Chinese characters with pinyin, preserving other characters.
+/// Polyphonic characters use their most common reading.
+#[tauri::command]
+pub fn to_pinyin(text: String, tone: ToneStyle) -> Result<String, AppError> {
+    let mut result = String::with_capacity(text.len() * 2);
+    // whether previous output was a syllable or an alphanumeric character
+    let mut prev_syllable = false;
+    let mut prev_word = false;
+
+    for (c, pinyin) in text.chars().zip(text.as_str().to_pinyin()) {
+        match pinyin {
+            Some(pinyin) => {
+                // separate syllables from each other and from adjacent words
+                if prev_word {
+                    result.push(' ');
+                }
+                result.push_str(match tone {
+                    ToneStyle::Marks => pinyin.with_tone(),
+                    ToneStyle::Numbers => pinyin.with_tone_num_end(),
+                    ToneStyle::None => pinyin.plain(),
+                });
+                prev_syllable = true;
+                prev_word = true;
+            }
+            None => {
+                // separate latin words and digits from preceding syllables
+                if prev_syllable && c.is_alphanumeric() {
+                    result.push(' ');
+                }
+                result.push(c);
+                prev_syllable = false;
+                prev_word = c.is_alphanumeric();
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+// accented latin letters and their ASCII transliterations, in lowercase
+const LATIN_TRANSLITERATIONS: &[(&str, &str)] = &[
+    ("àáâãäåāăą", "a"),
+    ("æ", "ae"),
+    ("çćĉċč", "c"),
+    ("ðďđ", "d"),
+    ("èéêëēĕėęě", "e"),
+    ("ĝğġģ", "g"),
+    ("ĥħ", "h"),
+    ("ìíîïĩīĭįı", "i"),
+    ("ĵ", "j"),
+    ("ķ", "k"),
+    ("ĺļľŀł", "l"),
+    ("ñńņňŉ", "n"),
+    ("òóôõöøōŏő", "o"),
+    ("œ", "oe"),
+    ("ŕŗř", "r"),
+    ("śŝşš", "s"),
+    ("ß", "ss"),
+    ("ţťŧ", "t"),
+    ("þ", "th"),
+    ("ùúûüũūŭůűų", "u"),
+    ("ŵ", "w"),
+    ("ýÿŷ", "y"),
+    ("źżž", "z"),
+];
+
+/// Get the ASCII transliteration of a lowercase accented latin letter.
+fn transliterate_latin(c: char) -> Option<&'static str> {
+    LATIN_TRANSLITERATIONS
+        .iter()
+        .find(|(letters, _)| letters.contains(c))
+        .map(|(_, ascii)| *ascii)
+}
+
+/// Check if a character is a combining diacritical mark, as found in decomposed text.
+fn is_combining_mark(c: char) -> bool {
+    matches!(c, '\u{0300}'..='\u{036F}')
+}
+
+/// Convert text to a URL slug: lowercase words joined by `separator` (default "-").
+/// Accented letters are transliterated and punctuation is removed. Chinese characters are
+/// kept as is, or converted to toneless pinyin when `pinyin` is true.
+/// Returns an empty string if no letters or digits remain.
+#[tauri::command]
+pub fn slugify(
+    text: String,
+    separator: Option<String>,
+    pinyin: Option<bool>,
+) -> Result<String, AppError> {
+    let separator = separator.unwrap_or_else(|| "-".to_string());
+    let pinyin = pinyin.unwrap_or(false);
+
+    let mut words: Vec<String> = Vec::new();
+    let mut word = String::new();
+    for (c, syllable) in text.chars().zip(text.as_str().to_pinyin()) {
+        match syllable {
+            // each syllable becomes its own word
+            Some(syllable) if pinyin => {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+                words.push(syllable.plain().to_string());
+            }
+            _ => {
+                for c in c.to_lowercase() {
+                    if let Some(ascii) = transliterate_latin(c) {
+                        word.push_str(ascii);
+                    } else if c.is_alphanumeric() {
+                        word.push(c);
+                    } else if !(is_combining_mark(c) || matches!(c, '\'' | '’')) {
+                        // combining marks and apostrophes are dropped without splitting words
+                        if !word.is_empty() {
+                            words.push(std::mem::take(&mut word));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    Ok(words.join(&separator))
+}
+
+/// Sanitize HTML before it is displayed, such as rich selections or rendered AI output.
+/// Only an allowlist of formatting tags and attributes is kept, so scripts, event handlers,
+/// iframes and `javascript:` links are stripped.
+#[tauri::command]
+pub fn sanitize_html(html: String) -> Result<String, AppError> {
+    Ok(ammonia::clean(&html))
+}
+
+/// Convert Markdown to readable plain text.
+/// Emphasis is unwrapped, list bullets are kept and links are rendered as "text (url)".
+#[tauri::command]
+pub fn markdown_to_text(md: String) -> Result<String, AppError> {
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+
+    let mut text = String::with_capacity(md.len());
+    // next number of each nested list (None for bullet lists)
+    let mut lists: Vec<Option<u64>> = Vec::new();
+    // destination and text start offset of the current link
+    let mut link: Option<(String, usize)> = None;
+
+    for event in Parser::new_ext(&md, options) {
+        match event {
+            Event::Start(Tag::List(start)) => lists.push(start),
+            Event::End(TagEnd::List(_)) => {
+                lists.pop();
+                if lists.is_empty() {
+                    end_block(&mut text, true);
+                }
+            }
+            Event::Start(Tag::Item) => {
+                end_block(&mut text, false);
+                text.push_str(&"  ".repeat(lists.len().saturating_sub(1)));
+                match lists.last_mut() {
+                    Some(Some(number)) => {
+                        text.push_str(&format!("{}. ", number));
+                        *number += 1;
+                    }
+                    _ => text.push_str("- "),
+                }
+            }
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                link = Some((dest_url.to_string(), text.len()));
+            }
+            Event::End(TagEnd::Link) => {
+                // skip destination when it equals the link text (autolinks)
+                if let Some((url, start)) = link.take() {
+                    if !url.is_empty() && text[start..] != url {
+                        text.push_str(&format!(" ({})", url));
+                    }
+                }
+            }
+            Event::Text(t) | Event::Code(t) => text.push_str(&t),
+            Event::SoftBreak | Event::HardBreak => text.push('\n'),
+            Event::TaskListMarker(checked) => text.push_str(if checked { "[x] " } else { "[ ] " }),
+            Event::End(TagEnd::Paragraph | TagEnd::Heading(_) | TagEnd::CodeBlock) => {
+                end_block(&mut text, lists.is_empty());
+            }
+            Event::End(TagEnd::TableCell) => text.push('\t'),
+            Event::End(TagEnd::TableHead | TagEnd::TableRow) => {
+                text.truncate(text.trim_end_matches('\t').len());
+                text.push('\n');
+            }
+            Event::End(TagEnd::Table) | Event::Rule => end_block(&mut text, true),
+            _ => {}
+        }
+    }
+
+    Ok(text.trim().to_string())
+}
+
+/// End current block with a line break, optionally followed by a blank line.
+fn end_block(text: &mut String, blank_line: bool) {
+    if text.is_empty() {
+        return;
+    }
+    if !text.ends_with('\n') {
+        text.push('\n');
+    }
+    if blank_line && !text.ends_with("\n\n") {
+        text.push('\n');
+    }
+}
+
+// diff output format
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffFormat {
+    Unified,
+    Inline,
+}
+
+/// Compare two texts and return a unified diff or inline HTML highlights.
+/// Inline output wraps removed text in `<del>` and added text in `<ins>`.
+#[tauri::command]
+pub fn diff_text(a: String, b: String, format: DiffFormat) -> Result<String, AppError> {
+    match format {
+        DiffFormat::Unified => Ok(TextDiff::from_lines(&a, &b)
+            .unified_diff()
+            .header("a", "b")
+            .to_string()),
+        DiffFormat::Inline => {
+            let diff = TextDiff::from_chars(&a, &b);
+            let mut html = String::new();
+            let mut current: Option<(ChangeTag, String)> = None;
+
+            // group consecutive changes with the same tag
+            for change in diff.iter_all_changes() {
+                match current.as_mut() {
+                    Some((tag, value)) if *tag == change.tag() => value.push_str(change.value()),
+                    _ => {
+                        if let Some((tag, value)) = current.take() {
+                            push_inline_change(&mut html, tag, &value);
+                        }
+                        current = Some((change.tag(), change.value().to_string()));
+                    }
+                }
+            }
+            if let Some((tag, value)) = current {
+                push_inline_change(&mut html, tag, &value);
+            }
+
+            Ok(html)
+        }
+    }
+}
+
+/// Append an escaped inline diff change to HTML output.
+fn push_inline_change(html: &mut String, tag: ChangeTag, value: &str) {
+    let value = escape_html(value);
+    match tag {
+        ChangeTag::Equal => html.push_str(&value),
+        ChangeTag::Delete => html.push_str(&format!("<del>{}</del>", value)),
+        ChangeTag::Insert => html.push_str(&format!("<ins>{}</ins>", value)),
+    }
+}
+
+/// Escape HTML special characters.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// line ending style
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LineEnding {
+    Lf,
+    Crlf,
+    Cr,
+}
+
+/// Convert all line endings in text to the given style.
+#[tauri::command]
+pub fn convert_line_endings(text: String, to: LineEnding) -> Result<String, AppError> {
+    let newline = match to {
+        LineEnding::Lf => "\n",
+        LineEnding::Crlf => "\r\n",
+        LineEnding::Cr => "\r",
+    };
+
+    // normalize to LF first so CRLF is not converted twice
+    let normalized = text.replace("\r\n", "\n").replace('\r', "\n");
+    Ok(match to {
+        LineEnding::Lf => normalized,
+        _ => normalized.replace('\n', newline),
+    })
+}
+
+/// Detect the line ending style of text.
+/// Returns "lf", "crlf" or "cr", "mixed" if several styles are used, or "none" without line breaks.
+#[tauri::command]
+pub fn detect_line_endings(text: String) -> String {
+    let crlf = text.matches("\r\n").count();
+    let lf = text.matches('\n').count() - crlf;
+    let cr = text.matches('\r').count() - crlf;
+
+    let styles = [("lf", lf), ("crlf", crlf), ("cr", cr)];
+    let mut used = styles.iter().filter(|(_, count)| *count > 0);
+    match (used.next(), used.next()) {
+        (None, _) => "none",
+        (Some((style, _)), None) => *style,
+        _ => "mixed",
+    }
+    .to_string()
+}
+
+// columns per indentation level and per tab
+const INDENT_WIDTH: usize = 4;
+
+/// Re-wrap paragraphs to a column width, counting East Asian wide characters as two columns.
+/// Blank lines, fenced code blocks and indented code lines are kept as is.
+#[tauri::command]
+pub fn reflow_text(text: String, width: usize) -> Result<String, AppError> {
+    if width == 0 {
+        return Err(current_locale()
+            .pick("Width must be greater than zero", "宽度必须大于零")
+            .into());
+    }
+
+    // keep original line ending style and trailing newline
+    let newline = if text.contains("\r\n") { "\r\n" } else { "\n" };
+    let trailing_newline = text.ends_with('\n');
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut in_fence = false;
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        let is_fence = trimmed.starts_with("```") || trimmed.starts_with("~~~");
+        let is_code = line.starts_with("    ") || line.starts_with('\t');
+        if in_fence || is_fence || is_code || trimmed.is_empty() {
+            lines.extend(wrap_paragraph(&paragraph, width));
+            paragraph.clear();
+            if is_fence {
+                in_fence = !in_fence;
+            }
+            lines.push(line.to_string());
+        } else {
+            paragraph.push(line);
+        }
+    }
+    lines.extend(wrap_paragraph(&paragraph, width));
+
+    let mut result = lines.join(newline);
+    if trailing_newline {
+        result.push_str(newline);
+    }
+    Ok(result)
+}
+
+/// Wrap the lines of a paragraph to a column width.
+/// Lines are only broken at whitespace or next to wide characters, and words
+/// longer than the width are kept on their own line.
+fn wrap_paragraph(lines: &[&str], width: usize) -> Vec<String> {
+    // join lines, without adding spaces between wide characters such as CJK
+    let mut joined = String::new();
+    for line in lines {
+        let line = line.trim();
+        if let (Some(last), Some(first)) = (joined.chars().last(), line.chars().next()) {
+            if char_width(last) < 2 || char_width(first) < 2 {
+                joined.push(' ');
+            }
+        }
+        joined.push_str(line);
+    }
+
+    // group word segments into unbreakable units, remembering preceding spaces
+    let mut units: Vec<(bool, String)> = Vec::new();
+    let mut space_before = false;
+    for segment in joined.split_word_bounds() {
+        if segment.trim().is_empty() {
+            space_before = true;
+            continue;
+        }
+        let breakable = space_before
+            || units.last().is_some_and(|(_, unit)| {
+                unit.chars().last().is_some_and(|c| char_width(c) > 1)
+                    || segment.chars().next().is_some_and(|c| char_width(c) > 1)
+            });
+        match units.last_mut() {
+            Some((_, unit)) if !breakable => unit.push_str(segment),
+            _ => units.push((space_before, segment.to_string())),
+        }
+        space_before = false;
+    }
+
+    let mut wrapped = Vec::new();
+    let mut current = String::new();
+    let mut current_width = 0;
+    for (space_before, unit) in units {
+        let unit_width = str_width(&unit);
+        let space = usize::from(space_before && !current.is_empty());
+        if !current.is_empty() && current_width + space + unit_width > width {
+            wrapped.push(std::mem::take(&mut current));
+            current_width = 0;
+        } else if space == 1 {
+            current.push(' ');
+            current_width += 1;
+        }
+        current.push_str(&unit);
+        current_width += unit_width;
+    }
+    if !current.is_empty() {
+        wrapped.push(current);
+    }
+    wrapped
+}
+
+/// Get the display width of text in columns.
+fn str_width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+/// Get the display width of a character in columns.
+/// East Asian wide characters and emoji take two columns, combining marks take none.
+fn char_width(c: char) -> usize {
+    match c as u32 {
+        0x0300..=0x036F | 0x200B..=0x200F | 0xFE00..=0xFE0F => 0,
+        0x1100..=0x115F
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F
+        | 0x1F900..=0x1F9FF
+        | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+/// Indent (positive `delta`) or dedent (negative `delta`) non-blank lines by indentation levels.
+/// Leading whitespace is rewritten with tabs or spaces, and dedenting stops at column zero.
+#[tauri::command]
+pub fn change_indent(text: String, delta: i32, use_tabs: bool) -> Result<String, AppError> {
+    // keep original line ending style and trailing newline
+    let newline = if text.contains("\r\n") { "\r\n" } else { "\n" };
+    let trailing_newline = text.ends_with('\n');
+
+    let shift = delta.unsigned_abs() as usize * INDENT_WIDTH;
+    let lines: Vec<String> = text
+        .lines()
+        .map(|line| {
+            let content = line.trim_start_matches([' ', '\t']);
+            if content.is_empty() {
+                return line.to_string();
+            }
+
+            // measure current indentation in columns, expanding tabs
+            let indent = line[..line.len() - content.len()]
+                .chars()
+                .fold(0, |columns, c| match c {
+                    '\t' => (columns / INDENT_WIDTH + 1) * INDENT_WIDTH,
+                    _ => columns + 1,
+                });
+            let indent = if delta >= 0 {
+                indent + shift
+            } else {
+                indent.saturating_sub(shift)
+            };
+
+            let whitespace = if use_tabs {
+                format!(
+                    "{}{}",
+                    "\t".repeat(indent / INDENT_WIDTH),
+                    " ".repeat(indent % INDENT_WIDTH)
+                )
+            } else {
+                " ".repeat(indent)
+            };
+            format!("{}{}", whitespace, content)
+        })
+        .collect();
+
+    let mut result = lines.join(newline);
+    if trailing_newline {
+        result.push_str(newline);
+    }
+    Ok(result)
+}
+
+// comment syntax of a language
+#[derive(Debug, Clone, Copy)]
+enum CommentSyntax {
+    Line(&'static str),
+    Block(&'static str, &'static str),
+}
+
+// comment syntax keyed by language name, add new languages here
+const COMMENT_SYNTAXES: &[(&str, CommentSyntax)] = &[
+    ("rust", CommentSyntax::Line("//")),
+    ("js", CommentSyntax::Line("//")),
+    ("ts", CommentSyntax::Line("//")),
+    ("c", CommentSyntax::Line("//")),
+    ("cpp", CommentSyntax::Line("//")),
+    ("java", CommentSyntax::Line("//")),
+    ("go", CommentSyntax::Line("//")),
+    ("python", CommentSyntax::Line("#")),
+    ("shell", CommentSyntax::Line("#")),
+    ("yaml", CommentSyntax::Line("#")),
+    ("sql", CommentSyntax::Line("--")),
+    ("lua", CommentSyntax::Line("--")),
+    ("html", CommentSyntax::Block("<!--", "-->")),
+    ("xml", CommentSyntax::Block("<!--", "-->")),
+    ("css", CommentSyntax::Block("/*", "*/")),
+];
+
+/// Comment or uncomment text with the comment syntax of the given language.
+/// Text is uncommented if every non-blank line (or the whole text, for block comments)
+/// is already commented, otherwise it is commented.
+#[tauri::command]
+pub fn toggle_comment(text: String, lang: String) -> Result<String, AppError> {
+    let syntax = COMMENT_SYNTAXES
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(lang.trim()))
+        .map(|(_, syntax)| *syntax)
+        .ok_or_else(|| labeled("Unsupported language", "不支持的语言", lang))?;
+
+    match syntax {
+        CommentSyntax::Line(prefix) => Ok(toggle_line_comment(&text, prefix)),
+        CommentSyntax::Block(open, close) => Ok(toggle_block_comment(&text, open, close)),
+    }
+}
+
+/// Toggle a line comment prefix on every non-blank line.
+/// Prefixes are inserted at the smallest indentation so the block stays aligned.
+fn toggle_line_comment(text: &str, prefix: &str) -> String {
+    // keep original line ending style and trailing newline
+    let newline = if text.contains("\r\n") { "\r\n" } else { "\n" };
+    let trailing_newline = text.ends_with('\n');
+
+    let lines: Vec<&str> = text.lines().collect();
+    let code_lines = || lines.iter().filter(|line| !line.trim().is_empty());
+    if code_lines().next().is_none() {
+        return text.to_string();
+    }
+    let commented = code_lines().all(|line| line.trim_start().starts_with(prefix));
+    // only count ASCII indentation, so slicing at it never splits a character
+    let indent = code_lines()
+        .map(|line| line.len() - line.trim_start_matches([' ', '\t']).len())
+        .min()
+        .unwrap_or(0);
+
+    let lines: Vec<String> = lines
+        .iter()
+        .map(|line| {
+            if line.trim().is_empty() {
+                return line.to_string();
+            }
+            if commented {
+                // remove prefix and one following space
+                let content = line.trim_start();
+                let rest = &content[prefix.len()..];
+                let rest = rest.strip_prefix(' ').unwrap_or(rest);
+                format!("{}{}", &line[..line.len() - content.len()], rest)
+            } else {
+                format!("{}{} {}", &line[..indent], prefix, &line[indent..])
+            }
+        })
+        .collect();
+
+    let mut result = lines.join(newline);
+    if trailing_newline {
+        result.push_str(newline);
+    }
+    result
+}
+
+/// Toggle a block comment around text, keeping surrounding whitespace.
+fn toggle_block_comment(text: &str, open: &str, close: &str) -> String {
+    let content = text.trim();
+    if content.is_empty() {
+        return text.to_string();
+    }
+    let start = text.len() - text.trim_start().len();
+    let (leading, trailing) = (&text[..start], &text[start + content.len()..]);
+
+    let inner = content
+        .strip_prefix(open)
+        .and_then(|rest| rest.strip_suffix(close));
+    match inner {
+        Some(inner) => {
+            // remove one space padding on each side
+            let inner = inner.strip_prefix(' ').unwrap_or(inner);
+            let inner = inner.strip_suffix(' ').unwrap_or(inner);
+            format!("{}{}{}", leading, inner, trailing)
+        }
+        None => format!("{}{} {} {}{}", leading, open, content, close, trailing),
+    }
+}
+
+// line processing operation
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LineOp {
+    Sort,
+    SortDesc,
+    Dedupe,
+    Reverse,
+    Shuffle,
+    Number,
+}
+
+/// Process lines of text by applying the given operations in order.
+#[tauri::command]
+pub fn process_lines(text: String, ops: Vec<LineOp>) -> Result<String, AppError> {
+    // keep original line ending style and trailing newline
+    let newline = if text.contains("\r\n") { "\r\n" } else { "\n" };
+    let trailing_newline = text.ends_with('\n');
+
+    let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+    for op in ops {
+        match op {
+            LineOp::Sort => sort_lines(&mut lines, false),
+            LineOp::SortDesc => sort_lines(&mut lines, true),
+            LineOp::Dedupe => {
+                let mut seen = HashSet::new();
+                lines.retain(|line| seen.insert(line.clone()));
+            }
+            LineOp::Reverse => lines.reverse(),
+            LineOp::Shuffle => shuffle(&mut lines),
+            LineOp::Number => {
+                let width = lines.len().to_string().len();
+                for (i, line) in lines.iter_mut().enumerate() {
+                    *line = format!("{:>width$}. {}", i + 1, line, width = width);
+                }
+            }
+        }
+    }
+
+    let mut result = lines.join(newline);
+    if trailing_newline {
+        result.push_str(newline);
+    }
+    Ok(result)
+}
+
+/// Sort lines by their folded sort keys, falling back to the original form for ties.
+/// This is not full locale collation: accented latin letters sort with their base letter,
+/// while other scripts such as CJK are ordered by code point rather than pinyin or strokes.
+fn sort_lines(lines: &mut Vec<String>, descending: bool) {
+    let mut keyed: Vec<(String, String)> = lines
+        .drain(..)
+        .map(|line| (sort_key(&line), line))
+        .collect();
+    // sort is stable, so equal lines keep their relative order
+    keyed.sort_by(|(a_key, a), (b_key, b)| {
+        let ordering = compare_lines(a_key, b_key).then_with(|| a.cmp(b));
+        if descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+    lines.extend(keyed.into_iter().map(|(_, line)| line));
+}
+
+/// Fold a line for sorting: lowercase it, drop combining marks and transliterate accented
+/// latin letters, so "Émile", "émile" and decomposed "e\u{301}mile" sort together.
+fn sort_key(line: &str) -> String {
+    let mut key = String::with_capacity(line.len());
+    for c in line.chars().flat_map(char::to_lowercase) {
+        if let Some(ascii) = transliterate_latin(c) {
+            key.push_str(ascii);
+        } else if !is_combining_mark(c) {
+            key.push(c);
+        }
+    }
+    key
+}
+
+/// Compare folded sort keys, with digit runs compared numerically so "item2" sorts
+/// before "item10".
+fn compare_lines(a: &str, b: &str) -> Ordering {
+    let mut a_chars = a.chars().peekable();
+    let mut b_chars = b.chars().peekable();
+
+    loop {
+        match (a_chars.peek().copied(), b_chars.peek().copied()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                // compare whole digit runs by numeric value
+                let x_digits: String =
+                    std::iter::from_fn(|| a_chars.next_if(char::is_ascii_digit)).collect();
+                let y_digits: String =
+                    std::iter::from_fn(|| b_chars.next_if(char::is_ascii_digit)).collect();
+                let x_trimmed = x_digits.trim_start_matches('0');
+                let y_trimmed = y_digits.trim_start_matches('0');
+                let ordering = x_trimmed
+                    .len()
+                    .cmp(&y_trimmed.len())
+                    .then_with(|| x_trimmed.cmp(y_trimmed));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(x), Some(y)) => {
+                if x != y {
+                    return x.cmp(&y);
+                }
+                a_chars.next();
+                b_chars.next();
+            }
+        }
+    }
+}
+
+/// Shuffle lines in place using Fisher-Yates with a randomly seeded hasher.
+fn shuffle(lines: &mut [String]) {
+    let state = RandomState::new();
+    for i in (1..lines.len()).rev() {
+        let mut hasher = state.build_hasher();
+        hasher.write_usize(i);
+        let j = (hasher.finish() % (i as u64 + 1)) as usize;
+        lines.swap(i, j);
+    }
+}
+
+/// Extract a single column from CSV/TSV text, joined by newlines.
+/// Rows without the requested column yield an empty line.
+#[tauri::command]
+pub fn extract_column(
+    text: String,
+    delimiter: String,
+    index: usize,
+    has_header: bool,
+) -> Result<String, AppError> {
+    // accept escaped tab for TSV
+    let delimiter = match delimiter.as_str() {
+        "\\t" | "tab" => b'\t',
+        d if d.len() == 1 => d.as_bytes()[0],
+        _ => return Err(labeled("Unsupported delimiter", "不支持的分隔符", delimiter).into()),
+    };
+
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(has_header)
+        .flexible(true)
+        .from_reader(text.as_bytes());
+
+    let mut column = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        column.push(record.get(index).unwrap_or_default().to_string());
+    }
+
+    Ok(column.join("\n"))
+}
+
+// unix timestamps above this absolute value are treated as milliseconds
+const UNIX_MILLIS_THRESHOLD: i64 = 100_000_000_000;
+
+// naive datetime formats accepted in addition to RFC 3339
+const NAIVE_DATETIME_FORMATS: &[&str] = &[
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M",
+    "%Y/%m/%d %H:%M:%S",
+];
+
+// input timestamp format
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimestampFormat {
+    Unix,
+    Iso,
+    Auto,
+}
+
+// timezone used to interpret and render timestamps
+#[derive(Debug, Clone, Copy)]
+enum Zone {
+    Local,
+    Fixed(FixedOffset),
+}
+
+/// Convert a timestamp between Unix epoch (seconds or milliseconds) and human formats.
+/// - `out_fmt`: "unix", "unix_ms", "iso" or a strftime format (e.g. "%Y-%m-%d %H:%M:%S")
+/// - `timezone`: "local" (default), "utc" or a fixed offset like "+08:00"
+#[tauri::command]
+pub fn format_timestamp(
+    input: String,
+    in_fmt: TimestampFormat,
+    out_fmt: String,
+    timezone: Option<String>,
+) -> Result<String, AppError> {
+    let input = input.trim();
+    let zone = parse_zone(timezone.as_deref().unwrap_or("local"))?;
+
+    // parse input into a UTC datetime
+    let datetime = match in_fmt {
+        TimestampFormat::Unix => parse_unix(input),
+        TimestampFormat::Iso => parse_iso(input, zone),
+        TimestampFormat::Auto => parse_unix(input).or_else(|| parse_iso(input, zone)),
+    }
+    .ok_or_else(|| labeled("Unrecognized timestamp", "无法识别的时间戳", input))?;
+
+    match zone {
+        Zone::Local => render_datetime(datetime.with_timezone(&Local), &out_fmt),
+        Zone::Fixed(offset) => render_datetime(datetime.with_timezone(&offset), &out_fmt),
+    }
+}
+
+/// Parse timezone name or fixed offset.
+fn parse_zone(timezone: &str) -> Result<Zone, AppError> {
+    match timezone.to_lowercase().as_str() {
+        "local" => Ok(Zone::Local),
+        "utc" | "z" => Ok(Zone::Fixed(
+            FixedOffset::east_opt(0).ok_or("Invalid offset")?,
+        )),
+        offset => offset
+            .parse::<FixedOffset>()
+            .map(Zone::Fixed)
+            .map_err(|_| labeled("Unsupported timezone", "不支持的时区", timezone).into()),
+    }
+}
+
+/// Parse Unix epoch seconds or milliseconds, with optional fractional seconds.
+fn parse_unix(input: &str) -> Option<DateTime<Utc>> {
+    if let Ok(value) = input.parse::<i64>() {
+        return if value.abs() >= UNIX_MILLIS_THRESHOLD {
+            DateTime::from_timestamp_millis(value)
+        } else {
+            DateTime::from_timestamp(value, 0)
+        };
+    }
+
+    let value = input.parse::<f64>().ok().filter(|v| v.is_finite())?;
+    DateTime::from_timestamp_millis((value * 1000.0).round() as i64)
+}
+
+/// Parse RFC 3339 datetime, or a naive datetime interpreted in the given timezone.
+fn parse_iso(input: &str, zone: Zone) -> Option<DateTime<Utc>> {
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(input) {
+        return Some(datetime.with_timezone(&Utc));
+    }
+
+    let naive = NAIVE_DATETIME_FORMATS
+        .iter()
+        .find_map(|fmt| NaiveDateTime::parse_from_str(input, fmt).ok())?;
+    match zone {
+        Zone::Local => Local
+            .from_local_datetime(&naive)
+            .earliest()
+            .map(|datetime| datetime.with_timezone(&Utc)),
+        Zone::Fixed(offset) => offset
+            .from_local_datetime(&naive)
+            .earliest()
+            .map(|datetime| datetime.with_timezone(&Utc)),
+    }
+}
+
+/// Render datetime with the given output format.
+fn render_datetime<Tz: TimeZone>(datetime: DateTime<Tz>, out_fmt: &str) -> Result<String, AppError>
+where
+    Tz::Offset: std::fmt::Display,
+{
+    match out_fmt {
+        "unix" => Ok(datetime.timestamp().to_string()),
+        "unix_ms" => Ok(datetime.timestamp_millis().to_string()),
+        "iso" => Ok(datetime.to_rfc3339()),
+        fmt => {
+            // reject invalid format strings instead of panicking while formatting
+            if StrftimeItems::new(fmt).any(|item| matches!(item, Item::Error)) {
+                return Err(labeled("Invalid datetime format", "无效的日期时间格式", fmt).into());
+            }
+            Ok(datetime.format(fmt).to_string())
+        }
+    }
+}
+
+// number conversion operation
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NumberOp {
+    ThousandsSep,
+    ToHex,
+    ToBinary,
+    FromHex,
+}
+
+/// Convert the first number found in text, keeping the surrounding text.
+/// - `thousands_sep`: insert thousands separators into a decimal number
+/// - `to_hex`/`to_binary`: convert a decimal integer to hexadecimal/binary
+/// - `from_hex`: convert a hexadecimal integer (with optional "0x" prefix) to decimal
+#[tauri::command]
+pub fn convert_number(text: String, op: NumberOp) -> Result<String, AppError> {
+    let hex = matches!(op, NumberOp::FromHex);
+    let (start, end) = find_number(&text, hex)
+        .ok_or_else(|| current_locale().pick("No number found in text", "文本中未找到数字"))?;
+    let number = &text[start..end];
+
+    let converted = match op {
+        NumberOp::ThousandsSep => insert_thousands_separators(number),
+        NumberOp::ToHex | NumberOp::ToBinary => {
+            let value = number
+                .parse::<i128>()
+                .map_err(|_| labeled("Not an integer", "不是整数", number))?;
+            let sign = if value < 0 { "-" } else { "" };
+            match op {
+                NumberOp::ToHex => format!("{}0x{:X}", sign, value.unsigned_abs()),
+                _ => format!("{}0b{:b}", sign, value.unsigned_abs()),
+            }
+        }
+        NumberOp::FromHex => {
+            let digits = number.trim_start_matches("0x").trim_start_matches("0X");
+            u128::from_str_radix(digits, 16)
+                .map_err(|_| labeled("Not a hexadecimal integer", "不是十六进制整数", number))?
+                .to_string()
+        }
+    };
+
+    Ok(format!("{}{}{}", &text[..start], converted, &text[end..]))
+}
+
+/// Find byte range of the first decimal number (or hexadecimal integer) in text.
+fn find_number(text: &str, hex: bool) -> Option<(usize, usize)> {
+    let bytes = text.as_bytes();
+
+    if hex {
+        // prefer hex digits with an explicit "0x" prefix
+        if let Some(prefix) = text.find("0x").or_else(|| text.find("0X")) {
+            let end = bytes[prefix + 2..]
+                .iter()
+                .position(|&b| !b.is_ascii_hexdigit())
+                .map_or(bytes.len(), |p| prefix + 2 + p);
+            if end > prefix + 2 {
+                return Some((prefix, end));
+            }
+        }
+
+        // otherwise take the first standalone word made of hex digits
+        return text
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .find(|word| !word.is_empty() && word.bytes().all(|b| b.is_ascii_hexdigit()))
+            .map(|word| {
+                let start = word.as_ptr() as usize - text.as_ptr() as usize;
+                (start, start + word.len())
+            });
+    }
+
+    let mut start = bytes.iter().position(u8::is_ascii_digit)?;
+    let mut end = bytes[start..]
+        .iter()
+        .position(|b| !b.is_ascii_digit())
+        .map_or(bytes.len(), |p| start + p);
+
+    // include fractional part
+    if end + 1 < bytes.len() && bytes[end] == b'.' && bytes[end + 1].is_ascii_digit() {
+        end = bytes[end + 1..]
+            .iter()
+            .position(|b| !b.is_ascii_digit())
+            .map_or(bytes.len(), |p| end + 1 + p);
+    }
+
+    // include minus sign
+    if start > 0 && bytes[start - 1] == b'-' {
+        start -= 1;
+    }
+
+    Some((start, end))
+}
+
+/// Insert thousands separators into the integer part of a decimal number.
+fn insert_thousands_separators(number: &str) -> String {
+    let (sign, number) = match number.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", number),
+    };
+    let (integer, fraction) = match number.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (number, None),
+    };
+
+    let mut grouped = String::with_capacity(integer.len() + integer.len() / 3);
+    for (i, c) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+
+    match fraction {
+        Some(fraction) => format!("{}{}.{}", sign, grouped, fraction),
+        None => format!("{}{}", sign, grouped),
+    }
+}
+
+// categories of invisible characters removed by `sanitize_text`
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SanitizeOptions {
+    pub zero_width: bool,
+    pub control: bool,
+    pub bidi: bool,
+}
+
+impl Default for SanitizeOptions {
+    fn default() -> Self {
+        SanitizeOptions {
+            zero_width: true,
+            control: true,
+            bidi: true,
+        }
+    }
+}
+
+/// Remove invisible characters that pollute captured text.
+/// - `zero_width`: zero-width spaces, word joiners, byte order marks and soft hyphens
+/// - `control`: control characters other than tabs and line breaks
+/// - `bidi`: bidirectional marks, embeddings, overrides and isolates
+///
+/// Zero-width joiners are kept since they are part of emoji sequences and some scripts.
+#[tauri::command]
+pub fn sanitize_text(text: String, options: Option<SanitizeOptions>) -> String {
+    let options = options.unwrap_or_default();
+    text.chars()
+        .filter(|&c| {
+            !(options.zero_width && is_zero_width(c)
+                || options.control && c.is_control() && !matches!(c, '\t' | '\n' | '\r')
+                || options.bidi && is_bidi_mark(c))
+        })
+        .collect()
+}
+
+/// Check if a character is an invisible zero-width character.
+fn is_zero_width(c: char) -> bool {
+    matches!(c, '\u{200B}' | '\u{2060}' | '\u{FEFF}' | '\u{00AD}')
+}
+
+/// Check if a character is a bidirectional formatting character.
+fn is_bidi_mark(c: char) -> bool {
+    matches!(
+        c,
+        '\u{061C}' | '\u{200E}' | '\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}'
+    )
+}
+
+// string literal syntax for escaping
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EscapeLang {
+    Json,
+    C,
+    Shell,
+    Html,
+}
+
+// named HTML entities decoded by `unescape_string`
+const HTML_ENTITIES: &[(&str, char)] = &[
+    ("amp", '&'),
+    ("lt", '<'),
+    ("gt", '>'),
+    ("quot", '"'),
+    ("apos", '\''),
+    ("nbsp", '\u{00A0}'),
+    ("copy", '\u{00A9}'),
+    ("reg", '\u{00AE}'),
+    ("hellip", '\u{2026}'),
+    ("mdash", '\u{2014}'),
+    ("ndash", '\u{2013}'),
+];
+
+/// Escape text for embedding in a string literal.
+/// - `json`: JSON string content without surrounding quotes
+/// - `c`: C string content, with octal escapes for other control characters
+/// - `shell`: a single-quoted POSIX shell word
+/// - `html`: text with HTML special characters entity-encoded
+#[tauri::command]
+pub fn escape_string(text: String, lang: EscapeLang) -> Result<String, AppError> {
+    Ok(match lang {
+        EscapeLang::Json => {
+            let quoted = serde_json::to_string(&text)?;
+            quoted[1..quoted.len() - 1].to_string()
+        }
+        EscapeLang::C => {
+            let mut escaped = String::with_capacity(text.len());
+            for c in text.chars() {
+                match c {
+                    '\\' => escaped.push_str("\\\\"),
+                    '"' => escaped.push_str("\\\""),
+                    '\n' => escaped.push_str("\\n"),
+                    '\r' => escaped.push_str("\\r"),
+                    '\t' => escaped.push_str("\\t"),
+                    c if c.is_ascii_control() => escaped.push_str(&format!("\\{:03o}", c as u8)),
+                    c => escaped.push(c),
+                }
+            }
+            escaped
+        }
+        EscapeLang::Shell => format!("'{}'", text.replace('\'', "'\\''")),
+        EscapeLang::Html => escape_html(&text).replace('\'', "&#39;"),
+    })
+}
+
+/// Unescape string literal content, the inverse of `escape_string`.
+/// JSON and C input may include the surrounding double quotes, and shell input may
+/// mix single quotes, double quotes and backslash escapes.
+#[tauri::command]
+pub fn unescape_string(text: String, lang: EscapeLang) -> Result<String, AppError> {
+    match lang {
+        EscapeLang::Json => {
+            let quoted = if is_double_quoted(&text) {
+                text
+            } else {
+                format!("\"{}\"", text)
+            };
+            Ok(serde_json::from_str(&quoted)?)
+        }
+        EscapeLang::C => {
+            let content = if is_double_quoted(&text) {
+                &text[1..text.len() - 1]
+            } else {
+                text.as_str()
+            };
+            unescape_c(content)
+        }
+        EscapeLang::Shell => unescape_shell(&text),
+        EscapeLang::Html => Ok(unescape_html(&text)),
+    }
+}
+
+/// Check if text is wrapped in double quotes.
+fn is_double_quoted(text: &str) -> bool {
+    text.len() >= 2 && text.starts_with('"') && text.ends_with('"')
+}
+
+/// Unescape C string content, decoding octal and hex escapes as UTF-8 bytes.
+fn unescape_c(text: &str) -> Result<String, AppError> {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            let mut buf = [0; 4];
+            bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            continue;
+        }
+
+        let escape = chars
+            .next()
+            .ok_or_else(|| current_locale().pick("Incomplete escape sequence", "转义序列不完整"))?;
+        match escape {
+            'n' => bytes.push(b'\n'),
+            'r' => bytes.push(b'\r'),
+            't' => bytes.push(b'\t'),
+            'a' => bytes.push(0x07),
+            'b' => bytes.push(0x08),
+            'f' => bytes.push(0x0C),
+            'v' => bytes.push(0x0B),
+            '\\' | '"' | '\'' | '?' => bytes.push(escape as u8),
+            '0'..='7' => {
+                let mut digits = escape.to_string();
+                while digits.len() < 3 {
+                    match chars.next_if(|c| ('0'..='7').contains(c)) {
+                        Some(c) => digits.push(c),
+                        None => break,
+                    }
+                }
+                let value = u32::from_str_radix(&digits, 8).unwrap_or(0);
+                let value = u8::try_from(value).map_err(|_| {
+                    labeled(
+                        "Octal escape out of range",
+                        "八进制转义超出范围",
+                        format!("\\{}", digits),
+                    )
+                })?;
+                bytes.push(value);
+            }
+            'x' => {
+                let digits: String =
+                    std::iter::from_fn(|| chars.next_if(char::is_ascii_hexdigit)).collect();
+                let value = u8::from_str_radix(&digits, 16).map_err(|_| {
+                    labeled(
+                        "Invalid hex escape",
+                        "无效的十六进制转义",
+                        format!("\\x{}", digits),
+                    )
+                })?;
+                bytes.push(value);
+            }
+            'u' | 'U' => {
+                let len = if escape == 'u' { 4 } else { 8 };
+                let digits: String = chars.by_ref().take(len).collect();
+                let c = u32::from_str_radix(&digits, 16)
+                    .ok()
+                    .filter(|_| digits.len() == len)
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| {
+                        labeled(
+                            "Invalid unicode escape",
+                            "无效的 Unicode 转义",
+                            format!("\\{}{}", escape, digits),
+                        )
+                    })?;
+                let mut buf = [0; 4];
+                bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            }
+            other => {
+                return Err(labeled(
+                    "Unknown escape sequence",
+                    "未知的转义序列",
+                    format!("\\{}", other),
+                )
+                .into())
+            }
+        }
+    }
+
+    String::from_utf8(bytes).map_err(|_| {
+        current_locale()
+            .pick(
+                "Escaped bytes are not valid UTF-8",
+                "转义后的字节不是有效的 UTF-8",
+            )
+            .into()
+    })
+}
+
+/// Unescape a POSIX shell word, removing quotes and backslash escapes.
+fn unescape_shell(text: &str) -> Result<String, AppError> {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            // everything up to the closing single quote is literal
+            '\'' => loop {
+                match chars.next() {
+                    Some('\'') => break,
+                    Some(c) => result.push(c),
+                    None => {
+                        return Err(current_locale()
+                            .pick("Unterminated single quote", "单引号未闭合")
+                            .into())
+                    }
+                }
+            },
+            // backslash only escapes $, `, ", \ and newline inside double quotes
+            '"' => loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => match chars.next() {
+                        Some('\n') => {}
+                        Some(c @ ('$' | '`' | '"' | '\\')) => result.push(c),
+                        Some(c) => {
+                            result.push('\\');
+                            result.push(c);
+                        }
+                        None => {
+                            return Err(current_locale()
+                                .pick("Unterminated double quote", "双引号未闭合")
+                                .into())
+                        }
+                    },
+                    Some(c) => result.push(c),
+                    None => {
+                        return Err(current_locale()
+                            .pick("Unterminated double quote", "双引号未闭合")
+                            .into())
+                    }
+                }
+            },
+            '\\' => match chars.next() {
+                Some('\n') | None => {}
+                Some(c) => result.push(c),
+            },
+            c => result.push(c),
+        }
+    }
+    Ok(result)
+}
+
+/// Decode named and numeric HTML entities, leaving unknown entities untouched.
+fn unescape_html(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest[1..].find(';').and_then(|end| {
+            let entity = &rest[1..end + 1];
+            let c = match entity.strip_prefix('#') {
+                Some(number) => match number.strip_prefix(['x', 'X']) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                    None => number.parse::<u32>().ok(),
+                }
+                .and_then(char::from_u32),
+                None => HTML_ENTITIES
+                    .iter()
+                    .find(|(name, _)| *name == entity)
+                    .map(|(_, c)| *c),
+            }?;
+            Some((c, end + 2))
+        });
+        match decoded {
+            Some((c, len)) => {
+                result.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+// maximum number of regex matches returned for preview, all matches are still counted
+const MAX_REGEX_MATCHES: usize = 1000;
+
+// structure to hold a single regex match, with offsets in UTF-16 code units
+#[derive(Debug, Clone, Serialize)]
+pub struct RegexMatch {
+    pub start: usize,
+    pub end: usize,
+    pub text: String,
+    pub groups: Vec<Option<String>>,
+}
+
+// structure to hold regex matches found in text
+#[derive(Debug, Clone, Serialize)]
+pub struct RegexMatches {
+    pub count: usize,
+    pub matches: Vec<RegexMatch>,
+}
+
+/// Find all matches of a regular expression in text to preview them before replacing.
+/// `flags` may combine `i` (case-insensitive), `m` (multi-line), `s` (dot matches newline),
+/// `x` (ignore whitespace) and `U` (swap greedy). Offsets are UTF-16 code units like in
+/// JavaScript strings, and at most 1000 matches are returned.
+#[tauri::command]
+pub fn regex_matches(
+    text: String,
+    pattern: String,
+    flags: String,
+) -> Result<RegexMatches, AppError> {
+    let mut builder = RegexBuilder::new(&pattern);
+    for flag in flags.chars() {
+        match flag {
+            'i' => builder.case_insensitive(true),
+            'm' => builder.multi_line(true),
+            's' => builder.dot_matches_new_line(true),
+            'x' => builder.ignore_whitespace(true),
+            'U' => builder.swap_greed(true),
+            // global flag is implied since all matches are returned
+            'g' => &mut builder,
+            other => {
+                return Err(labeled("Unsupported regex flag", "不支持的正则标志", other).into())
+            }
+        };
+    }
+    let regex = builder
+        .build()
+        .map_err(|e| labeled("Invalid regex", "无效的正则表达式", e))?;
+
+    let mut count = 0;
+    let mut matches = Vec::new();
+    // convert byte offsets incrementally since matches are in order
+    let (mut byte_offset, mut utf16_offset) = (0, 0);
+    let mut to_utf16 = |byte: usize| {
+        utf16_offset += text[byte_offset..byte].encode_utf16().count();
+        byte_offset = byte;
+        utf16_offset
+    };
+
+    for captures in regex.captures_iter(&text) {
+        count += 1;
+        if matches.len() >= MAX_REGEX_MATCHES {
+            continue;
+        }
+
+        let whole = captures.get(0).ok_or("Missing regex match")?;
+        matches.push(RegexMatch {
+            start: to_utf16(whole.start()),
+            end: to_utf16(whole.end()),
+            text: whole.as_str().to_string(),
+            groups: captures
+                .iter()
+                .skip(1)
+                .map(|group| group.map(|group| group.as_str().to_string()))
+                .collect(),
+        });
+    }
+
+    Ok(RegexMatches { count, matches })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_chinese_converts_regional_vocabulary() {
+        let simplified = "用鼠标打开软件查看信息";
+        let taiwan = convert_chinese(simplified.into(), ChineseVariant::Taiwan).unwrap();
+        for phrase in ["滑鼠", "軟體", "資訊"] {
+            assert!(taiwan.contains(phrase), "{} in {}", phrase, taiwan);
+        }
+
+        let mainland = convert_chinese(taiwan, ChineseVariant::Mainland).unwrap();
+        for phrase in ["鼠标", "软件", "信息"] {
+            assert!(mainland.contains(phrase), "{} in {}", phrase, mainland);
+        }
+    }
+
+    #[test]
+    fn reflow_text_fits_lines_to_width() {
+        let text = "TextGO is a customizable text selection popup tool. 它可以在任意应用中\n\
+                    对选中的文本执行脚本、提示词和搜索，并把结果直接粘贴回去。Long links like \
+                    https://github.com/C5H12O5/TextGO/releases/latest stay intact.";
+        let width = 30;
+        let reflowed = reflow_text(text.into(), width).unwrap();
+
+        assert!(reflowed.lines().count() > 1);
+        for line in reflowed.lines() {
+            let single_word = !line.contains(' ') && str_width(line) == line.chars().count();
+            assert!(str_width(line) <= width || single_word, "{:?}", line);
+        }
+        let strip = |s: &str| s.split_whitespace().collect::<String>();
+        assert_eq!(strip(&reflowed), strip(text));
+    }
+
+    #[test]
+    fn reflow_text_keeps_code_and_blank_lines() {
+        let text = "first paragraph\nis joined\n\n```\nlet x = 1;\nlet y = 2;\n```\n\n    indented code\n    stays\n";
+        let reflowed = reflow_text(text.into(), 80).unwrap();
+        assert_eq!(
+            reflowed,
+            "first paragraph is joined\n\n```\nlet x = 1;\nlet y = 2;\n```\n\n    indented code\n    stays\n"
+        );
+    }
+
+    #[test]
+    fn change_indent_shifts_by_levels() {
+        let text = "a\n  b\n\n\tc\n";
+        assert_eq!(
+            change_indent(text.into(), 1, false).unwrap(),
+            "    a\n      b\n\n        c\n"
+        );
+        assert_eq!(
+            change_indent(text.into(), 1, true).unwrap(),
+            "\ta\n\t  b\n\n\t\tc\n"
+        );
+        assert_eq!(
+            change_indent(text.into(), -1, false).unwrap(),
+            "a\nb\n\nc\n"
+        );
+        assert_eq!(
+            change_indent("\t\ta\r\n".into(), -1, true).unwrap(),
+            "\ta\r\n"
+        );
+    }
+
+    #[test]
+    fn toggle_comment_round_trips_every_language() {
+        let text = "fn main() {\n    let x = 1;\n\n    x\n}\n";
+        for (lang, _) in COMMENT_SYNTAXES {
+            let commented = toggle_comment(text.into(), lang.to_string()).unwrap();
+            assert_ne!(commented, text, "{}", lang);
+            let uncommented = toggle_comment(commented, lang.to_string()).unwrap();
+            assert_eq!(uncommented, text, "{}", lang);
+        }
+    }
+
+    #[test]
+    fn toggle_comment_handles_unicode_indentation() {
+        let text = " a\n\u{3000}b";
+        let commented = toggle_comment(text.into(), "rust".into()).unwrap();
+        assert_eq!(commented, "//  a\n// \u{3000}b");
+        assert_eq!(toggle_comment(commented, "rust".into()).unwrap(), text);
+
+        let text = "  a\n  \u{3000}b";
+        let commented = toggle_comment(text.into(), "python".into()).unwrap();
+        assert_eq!(commented, "  # a\n  # \u{3000}b");
+        assert_eq!(toggle_comment(commented, "python".into()).unwrap(), text);
+    }
+
+    #[test]
+    fn process_lines_sorts_accented_and_cjk_lines() {
+        let text = "Zebra\n日本\néclair\napple\nrésumé\n中文\nE\u{301}mile\nresume\nÁbc\n";
+        assert_eq!(
+            process_lines(text.into(), vec![LineOp::Sort]).unwrap(),
+            "Ábc\napple\néclair\nE\u{301}mile\nresume\nrésumé\nZebra\n中文\n日本\n"
+        );
+        assert_eq!(
+            process_lines(text.into(), vec![LineOp::SortDesc]).unwrap(),
+            "日本\n中文\nZebra\nrésumé\nresume\nE\u{301}mile\néclair\napple\nÁbc\n"
+        );
+    }
+
+    #[test]
+    fn process_lines_sorts_digit_runs_numerically() {
+        let text = "item10\r\nitem2\r\nItem1\r\nitem2";
+        assert_eq!(
+            process_lines(
+                text.into(),
+                vec![LineOp::Sort, LineOp::Dedupe, LineOp::Number]
+            )
+            .unwrap(),
+            "1. Item1\r\n2. item2\r\n3. item10"
+        );
+    }
+
+    #[test]
+    fn convert_width_round_trips_ascii_and_symbols() {
+        let full = "ＡＢＣ　１２３！￥";
+        assert_eq!(
+            convert_width(full.into(), Width::Half).unwrap(),
+            "ABC 123!\u{00A5}"
+        );
+        assert_eq!(
+            convert_width("ABC 123!\u{00A5}".into(), Width::Full).unwrap(),
+            full
+        );
+        // characters without width variants are kept
+        assert_eq!(convert_width("中文ｶ".into(), Width::Half).unwrap(), "中文ｶ");
+    }
+
+    #[test]
+    fn to_pinyin_separates_syllables_and_words() {
+        let text = "中文abc123，你好";
+        assert_eq!(
+            to_pinyin(text.into(), ToneStyle::Marks).unwrap(),
+            "zhōng wén abc123，nǐ hǎo"
+        );
+        assert_eq!(
+            to_pinyin(text.into(), ToneStyle::Numbers).unwrap(),
+            "zhong1 wen2 abc123，ni3 hao3"
+        );
+        assert_eq!(
+            to_pinyin(text.into(), ToneStyle::None).unwrap(),
+            "zhong wen abc123，ni hao"
+        );
+    }
+
+    #[test]
+    fn markdown_to_text_keeps_lists_and_links() {
+        let md = "# Title\n\nSome **bold** and *italic* text with a [link](https://example.com) and <https://a.b>.\n\n1. one\n2. two\n   - nested\n\n- [x] done\n";
+        assert_eq!(
+            markdown_to_text(md.into()).unwrap(),
+            "Title\n\nSome bold and italic text with a link (https://example.com) and https://a.b.\n\n1. one\n2. two\n  - nested\n\n- [x] done"
+        );
+    }
+
+    #[test]
+    fn diff_text_renders_unified_and_inline() {
+        assert_eq!(
+            diff_text("a\nb\nc\n".into(), "a\nB\nc\n".into(), DiffFormat::Unified).unwrap(),
+            "--- a\n+++ b\n@@ -1,3 +1,3 @@\n a\n-b\n+B\n c\n"
+        );
+        assert_eq!(
+            diff_text("a < b".into(), "a > b".into(), DiffFormat::Inline).unwrap(),
+            "a <del>&lt;</del><ins>&gt;</ins> b"
+        );
+    }
+
+    #[test]
+    fn extract_column_handles_quotes_and_short_rows() {
+        let csv = "name,age\n\"Doe, J\",30\nSmith\n";
+        assert_eq!(
+            extract_column(csv.into(), ",".into(), 0, true).unwrap(),
+            "Doe, J\nSmith"
+        );
+        assert_eq!(
+            extract_column(csv.into(), ",".into(), 1, true).unwrap(),
+            "30\n"
+        );
+        assert_eq!(
+            extract_column("a\tb\nc\td".into(), "\\t".into(), 1, false).unwrap(),
+            "b\nd"
+        );
+        assert!(extract_column(csv.into(), "::".into(), 0, true).is_err());
+    }
+
+    #[test]
+    fn format_timestamp_converts_between_zones() {
+        assert_eq!(
+            format_timestamp(
+                "1700000000".into(),
+                TimestampFormat::Auto,
+                "iso".into(),
+                Some("utc".into())
+            )
+            .unwrap(),
+            "2023-11-14T22:13:20+00:00"
+        );
+        assert_eq!(
+            format_timestamp(
+                "1700000000123".into(),
+                TimestampFormat::Unix,
+                "%Y-%m-%d %H:%M:%S%.3f".into(),
+                Some("+08:00".into())
+            )
+            .unwrap(),
+            "2023-11-15 06:13:20.123"
+        );
+        assert_eq!(
+            format_timestamp(
+                "2023-11-15 06:13:20".into(),
+                TimestampFormat::Iso,
+                "unix".into(),
+                Some("+08:00".into())
+            )
+            .unwrap(),
+            "1700000000"
+        );
+        assert!(format_timestamp(
+            "not a date".into(),
+            TimestampFormat::Auto,
+            "iso".into(),
+            None
+        )
+        .is_err());
+        assert!(format_timestamp(
+            "0".into(),
+            TimestampFormat::Unix,
+            "iso".into(),
+            Some("mars".into())
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn convert_number_keeps_surrounding_text() {
+        let convert = |text: &str, op| convert_number(text.into(), op).unwrap();
+        assert_eq!(
+            convert("Total: 1234567.89 USD", NumberOp::ThousandsSep),
+            "Total: 1,234,567.89 USD"
+        );
+        assert_eq!(convert("-255 items", NumberOp::ToHex), "-0xFF items");
+        assert_eq!(convert("5", NumberOp::ToBinary), "0b101");
+        assert_eq!(convert("color 0xff00", NumberOp::FromHex), "color 65280");
+        assert_eq!(convert("id cafe", NumberOp::FromHex), "id 51966");
+        assert!(convert_number("none".into(), NumberOp::ToHex).is_err());
+    }
+
+    #[test]
+    fn sanitize_text_removes_selected_categories() {
+        let text = "a\u{200B}b\u{200D}c\u{202E}d\u{0007}e\tf\n";
+        assert_eq!(sanitize_text(text.into(), None), "ab\u{200D}cde\tf\n");
+
+        let options = SanitizeOptions {
+            zero_width: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            sanitize_text(text.into(), Some(options)),
+            "a\u{200B}b\u{200D}cde\tf\n"
+        );
+    }
+
+    #[test]
+    fn line_endings_are_detected_and_converted() {
+        assert_eq!(detect_line_endings("a\r\nb\r\n".into()), "crlf");
+        assert_eq!(detect_line_endings("a\rb".into()), "cr");
+        assert_eq!(detect_line_endings("a\nb\r\n".into()), "mixed");
+        assert_eq!(detect_line_endings("ab".into()), "none");
+
+        let text = "a\r\nb\rc\n";
+        assert_eq!(
+            convert_line_endings(text.into(), LineEnding::Lf).unwrap(),
+            "a\nb\nc\n"
+        );
+        assert_eq!(
+            convert_line_endings(text.into(), LineEnding::Crlf).unwrap(),
+            "a\r\nb\r\nc\r\n"
+        );
+    }
+
+    #[test]
+    fn slugify_transliterates_accents_and_pinyin() {
+        assert_eq!(
+            slugify("Crème Brûlée — Straße's Café!".into(), None, None).unwrap(),
+            "creme-brulee-strasses-cafe"
+        );
+        assert_eq!(
+            slugify("Cafe\u{301} Noir".into(), Some("_".into()), None).unwrap(),
+            "cafe_noir"
+        );
+        assert_eq!(
+            slugify("中文 Title".into(), None, None).unwrap(),
+            "中文-title"
+        );
+        assert_eq!(
+            slugify("中文 Title".into(), None, Some(true)).unwrap(),
+            "zhong-wen-title"
+        );
+        assert_eq!(slugify("!!!".into(), None, None).unwrap(), "");
+    }
+
+    #[test]
+    fn escape_string_round_trips_every_language() {
+        let text = "He said \"hi\"\n\ttab 'q' \\ <b>&\u{1}中";
+        for lang in [
+            EscapeLang::Json,
+            EscapeLang::C,
+            EscapeLang::Shell,
+            EscapeLang::Html,
+        ] {
+            let escaped = escape_string(text.into(), lang).unwrap();
+            assert_eq!(unescape_string(escaped, lang).unwrap(), text, "{:?}", lang);
+        }
+    }
+
+    #[test]
+    fn unescape_string_decodes_escapes() {
+        assert_eq!(
+            unescape_string("\"\\x41\\101\\u00e9\"".into(), EscapeLang::C).unwrap(),
+            "AAé"
+        );
+        assert_eq!(
+            unescape_string("'it'\\''s' \"$HOME\\$\"".into(), EscapeLang::Shell).unwrap(),
+            "it's $HOME$"
+        );
+        assert_eq!(
+            unescape_string("&lt;&#x4e2d;&unknown;".into(), EscapeLang::Html).unwrap(),
+            "<中&unknown;"
+        );
+        assert!(unescape_string("'abc".into(), EscapeLang::Shell).is_err());
+        assert!(unescape_string("\\q".into(), EscapeLang::C).is_err());
+    }
+
+    #[test]
+    fn regex_matches_reports_utf16_offsets_and_groups() {
+        let result = regex_matches("😀ab AB".into(), "(a)(x)?b".into(), "gi".into()).unwrap();
+        assert_eq!(result.count, 2);
+        assert_eq!(
+            result
+                .matches
+                .iter()
+                .map(|m| (m.start, m.end, m.text.as_str(), m.groups.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (2, 4, "ab", vec![Some("a".to_string()), None]),
+                (5, 7, "AB", vec![Some("A".to_string()), None]),
+            ]
+        );
+
+        assert!(regex_matches("a".into(), "a".into(), "q".into()).is_err());
+        assert!(regex_matches("a".into(), "(".into(), "".into()).is_err());
+    }
+
+    #[test]
+    fn regex_matches_caps_returned_matches() {
+        let result = regex_matches("a".repeat(1005), "a".into(), "".into()).unwrap();
+        assert_eq!(result.count, 1005);
+        assert_eq!(result.matches.len(), MAX_REGEX_MATCHES);
+    }
+}
@@ -1,8 +1,29 @@
+use crate::commands::locale::current_locale;
 use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
 use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
-use tauri::tray::TrayIconBuilder;
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
 use tauri::AppHandle;
 
+// action performed when the tray icon is left-clicked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrayLeftClickAction {
+    Menu,
+    Toggle,
+    Popup,
+}
+
+// left-click opens the menu on macOS and brings up the main window on Windows by default
+#[cfg(target_os = "windows")]
+const DEFAULT_LEFT_CLICK_ACTION: TrayLeftClickAction = TrayLeftClickAction::Popup;
+#[cfg(not(target_os = "windows"))]
+const DEFAULT_LEFT_CLICK_ACTION: TrayLeftClickAction = TrayLeftClickAction::Menu;
+
+// current tray left-click action
+static LEFT_CLICK_ACTION: Mutex<TrayLeftClickAction> = Mutex::new(DEFAULT_LEFT_CLICK_ACTION);
+
 /// Initialize or update tray menu.
 #[tauri::command]
 pub fn setup_tray(
@@ -46,7 +67,7 @@ pub fn setup_tray(
             .menu(&menu)
             .icon(app.default_window_icon().unwrap().clone())
             .icon_as_template(true)
-            .show_menu_on_left_click(cfg!(not(target_os = "windows")))
+            .show_menu_on_left_click(*LEFT_CLICK_ACTION.lock()? == TrayLeftClickAction::Menu)
             .on_menu_event(|app, event| match event.id.as_ref() {
                 "main_window" => {
                     crate::commands::show_main_window(app.clone());
@@ -67,39 +88,107 @@ pub fn setup_tray(
                     app.exit(0);
                 }
                 _ => {}
+            })
+            .on_tray_icon_event(|tray, event| {
+                if let TrayIconEvent::Click {
+                    button: MouseButton::Left,
+                    button_state: MouseButtonState::Up,
+                    ..
+                } = event
+                {
+                    handle_left_click(tray.app_handle());
+                }
             });
 
-        // on Windows, left click shows main window instead of opening the menu
-        #[cfg(target_os = "windows")]
-        let builder = builder.on_tray_icon_event(|tray, event| {
-            use tauri::tray::{MouseButton, MouseButtonState, TrayIconEvent};
-            if let TrayIconEvent::Click {
-                button: MouseButton::Left,
-                button_state: MouseButtonState::Up,
-                ..
-            } = event
-            {
-                crate::commands::show_main_window(tray.app_handle().clone());
-            }
-        });
-
         let _tray = builder.build(&app)?;
     }
 
     Ok(())
 }
 
-/// Show about dialog.
+/// Set the action performed when the tray icon is left-clicked.
+/// - `menu`: open the tray menu
+/// - `toggle`: show or hide the main window
+/// - `popup`: bring up the main window
+#[tauri::command]
+pub fn set_tray_left_click_action(
+    app: AppHandle,
+    action: TrayLeftClickAction,
+) -> Result<(), AppError> {
+    *LEFT_CLICK_ACTION.lock()? = action;
+
+    // update existing tray icon, otherwise it is applied when the tray is created
+    if let Some(tray) = app.tray_by_id("main-tray") {
+        tray.set_show_menu_on_left_click(action == TrayLeftClickAction::Menu)?;
+    }
+
+    Ok(())
+}
+
+/// Perform the configured tray left-click action.
+fn handle_left_click(app: &AppHandle) {
+    let action = LEFT_CLICK_ACTION
+        .lock()
+        .map_or(DEFAULT_LEFT_CLICK_ACTION, |action| *action);
+    match action {
+        // menu is opened by the tray icon itself
+        TrayLeftClickAction::Menu => {}
+        TrayLeftClickAction::Toggle => crate::commands::toggle_main_window(app.clone()),
+        TrayLeftClickAction::Popup => crate::commands::show_main_window(app.clone()),
+    }
+}
+
+// structure to hold system information for bug reports
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemInfo {
+    pub os: String,
+    pub os_version: String,
+    pub arch: String,
+    pub app_version: String,
+    pub webview_version: Option<String>,
+}
+
+/// Show about dialog without blocking the main thread.
 #[tauri::command]
 pub fn show_about(app: AppHandle) {
     use tauri_plugin_dialog::{DialogExt, MessageDialogKind};
 
     // get application information
     let package_info = app.package_info();
-    // use dialog plugin to show message box
+    let build = if cfg!(debug_assertions) {
+        "debug"
+    } else {
+        "release"
+    };
+    let locale = current_locale();
+    let message = format!(
+        "{} {}\n{} {} ({}-{})\n{}",
+        locale.pick("Version", "版本"),
+        package_info.version,
+        locale.pick("Build", "构建"),
+        build,
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        env!("CARGO_PKG_REPOSITORY"),
+    );
+
+    // use dialog plugin to show message box asynchronously
     app.dialog()
-        .message(format!("Version {}", package_info.version))
+        .message(message)
         .title(package_info.name.clone())
         .kind(MessageDialogKind::Info)
-        .blocking_show();
+        .show(|_| {});
+}
+
+/// Get operating system, architecture, application and webview versions for bug reports.
+#[tauri::command]
+pub fn get_system_info(app: AppHandle) -> SystemInfo {
+    SystemInfo {
+        os: tauri_plugin_os::type_().to_string(),
+        os_version: tauri_plugin_os::version().to_string(),
+        arch: tauri_plugin_os::arch().to_string(),
+        app_version: app.package_info().version.to_string(),
+        webview_version: tauri::webview_version().ok(),
+    }
 }
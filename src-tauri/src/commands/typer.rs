@@ -1,71 +1,457 @@
 use crate::commands::clipboard::{set_clipboard_text, with_clipboard_backup};
+use crate::commands::identifier::{get_editable_override, matches_wildcard};
 use crate::commands::keyboard::send_paste_keys;
-use crate::commands::shortcut::ShortcutHandlerGuard;
-use crate::error::AppError;
+use crate::commands::selection::{read_selection, SelectionMethod};
+use crate::commands::shortcut::{mark_synthetic_input, ShortcutHandlerGuard};
+use crate::commands::window::{hide_popup, restore_popup_focus};
+use crate::error::{AppError, ErrorKind};
 use crate::platform;
-use crate::ENIGO;
+use crate::{ENIGO, SETTINGS_STORE};
 use enigo::{Direction, Key, Keyboard};
-use std::time::Duration;
-use tauri::AppHandle;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Listener};
+use tauri_plugin_store::StoreExt;
+
+// interval between focus checks while waiting to enter text
+const FOCUS_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+// structure to describe what enter_text would do in dry-run mode
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnterTextPreview {
+    pub text: String,
+    pub chars: usize,
+    pub editable: bool,
+}
+
+// structure to describe the outcome of enter_text
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnterTextResult {
+    pub preview: Option<EnterTextPreview>,
+    pub inserted: Option<bool>,
+}
+
+// payload of the event emitted by the popup when an AI response has finished
+#[derive(Debug, Clone, Deserialize)]
+struct AiRequestDone {
+    text: String,
+}
+
+// how entered text is combined with the current selection
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EnterMode {
+    #[default]
+    Replace,
+    InsertBefore,
+    InsertAfter,
+    Wrap,
+}
+
+// options of enter_text, all disabled by default
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct EnterTextOptions {
+    pub dry_run: bool,
+    pub mode: EnterMode,
+    pub prefix: Option<String>,
+    pub suffix: Option<String>,
+    pub verify: bool,
+    pub retry_typing: bool,
+}
 
 /// Enter text and try to select it.
+/// - `replace`: replace the selection and select the entered text
+/// - `insert_before`/`insert_after`: move the caret next to the selection with the selection
+///   range APIs, insert text there and select the original range again
+/// - `wrap`: surround the selection with `prefix`/`suffix` and select the wrapped text;
+///   `text` must be empty
+///
+/// Insert and wrap modes enter text natively when possible, falling back to paste.
+/// Native entering is only supported on macOS, so Windows always pastes.
+///
+/// With `dryRun` nothing is entered, and a preview of the operation is returned instead.
+/// With `verify`, the focused text length is compared before and after pasting to report
+/// whether it grew by the entered length minus the replaced selection, and `retryTyping`
+/// types the text when the paste left the text unchanged without a selection.
 #[tauri::command]
 pub async fn enter_text(
     app: AppHandle,
     text: String,
     clipboard: Option<bool>,
-) -> Result<(), AppError> {
-    if text.is_empty() {
-        return Ok(());
+    options: Option<EnterTextOptions>,
+) -> Result<EnterTextResult, AppError> {
+    let EnterTextOptions {
+        dry_run,
+        mode,
+        prefix,
+        suffix,
+        verify,
+        retry_typing,
+    } = options.unwrap_or_default();
+
+    // wrap mode only enters prefix and suffix, reject text instead of dropping it
+    if matches!(mode, EnterMode::Wrap) && !text.is_empty() {
+        return Err("Text must be empty in wrap mode, use prefix and suffix instead".into());
     }
 
     // suspend shortcut handling to avoid interference
     let _guard = ShortcutHandlerGuard::suspend();
 
-    // calculate number of characters before moving text
+    // popup may be kept open for chained edits while holding keyboard focus
+    let keep_open = !dry_run
+        && app
+            .store(SETTINGS_STORE)?
+            .get("keepPopupOpen")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+    if keep_open {
+        // give focus back to the target field so the selection is read and pasted there
+        restore_popup_focus(&app).await?;
+    }
+
+    // compose full text to enter, only wrap mode includes the selection
+    let text = match mode {
+        EnterMode::Wrap => {
+            // avoid sending copy keys in dry-run mode
+            let method = if dry_run {
+                SelectionMethod::Native
+            } else {
+                SelectionMethod::Auto
+            };
+            let selection = read_selection(&app, false, method).await?;
+            format!(
+                "{}{}{}",
+                prefix.unwrap_or_default(),
+                selection,
+                suffix.unwrap_or_default()
+            )
+        }
+        _ => text,
+    };
     let chars = text.chars().count();
 
+    if dry_run {
+        return Ok(EnterTextResult {
+            preview: Some(EnterTextPreview {
+                chars,
+                editable: is_cursor_editable(&app).unwrap_or(false),
+                text,
+            }),
+            inserted: None,
+        });
+    }
+
+    if text.is_empty() {
+        return Ok(EnterTextResult::default());
+    }
+
+    // report entered text to the popup kept open for chained edits
+    let entered = keep_open.then(|| (app.clone(), text.clone()));
+
+    // insert modes collapse the caret next to the selection, so only the text is entered
+    // and the selection keeps its formatting, then select the original range again
+    let reselect = match mode {
+        EnterMode::InsertBefore | EnterMode::InsertAfter => {
+            let (location, length) = platform::get_selection_range()?;
+            if matches!(mode, EnterMode::InsertBefore) {
+                platform::set_selection_range(location, 0)?;
+                Some((location + text.encode_utf16().count(), length))
+            } else {
+                platform::set_selection_range(location + length, 0)?;
+                Some((location, length))
+            }
+        }
+        _ => None,
+    };
+
+    // first try using native API to replace selection or insert at the caret
+    let replaced = if matches!(mode, EnterMode::Replace) {
+        None
+    } else {
+        replace_natively(&text)
+    };
+    if replaced.is_some() {
+        select_entered_text(&app, reselect, chars)?;
+    }
+
     // core logic for entering text
     let do_enter_text = || async move {
+        // measure focused text before pasting for verification
+        let check = if verify { LengthCheck::measure() } else { None };
+
         // set clipboard text
-        set_clipboard_text(text)?;
+        set_clipboard_text(text.clone())?;
 
         // send paste shortcut
-        let _ = app.run_on_main_thread(|| {
-            let _ = send_paste_keys(Some(false), Some(true));
+        let app_handle = app.clone();
+        let _ = app.run_on_main_thread(move || {
+            let _ = send_paste_keys(app_handle, Some(false), Some(true));
         });
 
         // delay 100 ms to ensure paste operation completes
         tokio::time::sleep(Duration::from_millis(100)).await;
 
-        // if cursor position is editable, try to select entered text
-        if platform::is_cursor_editable()? {
-            // first try using native API to select text
-            if platform::select_backward_chars(chars).is_err() {
-                // if native API call fails and char count is <= 50, use keyboard simulation
-                if chars <= 50 {
-                    let mut enigo_guard = ENIGO.lock()?;
-                    let enigo = enigo_guard.as_mut()?;
-
-                    enigo.key(Key::Shift, Direction::Press)?;
-                    for _ in 0..chars {
-                        #[cfg(target_os = "windows")]
-                        std::thread::sleep(Duration::from_millis(5));
-
-                        enigo.key(Key::LeftArrow, Direction::Click)?;
-                    }
-                    enigo.key(Key::Shift, Direction::Release)?;
+        // verify that focused text length changed by the entered length
+        let inserted = match check {
+            Some(check) => {
+                // retry by typing text directly only if paste was clearly rejected,
+                // so text replacing a selection of the same length is not entered twice
+                if retry_typing && check.is_unchanged() {
+                    warn!("Paste was not applied, retrying by typing text");
+                    type_text(&text)?;
                 }
+
+                check.is_entered(&text)
             }
-        }
+            None => None,
+        };
+
+        select_entered_text(&app, reselect, chars)?;
 
-        Ok(())
+        Ok(inserted)
     };
 
     // keep text in clipboard if clipboard is true, otherwise backup and restore
-    if clipboard.unwrap_or(false) {
-        do_enter_text().await
+    let inserted = if let Some(inserted) = replaced {
+        inserted.filter(|_| verify)
+    } else if clipboard.unwrap_or(false) {
+        do_enter_text().await?
     } else {
-        with_clipboard_backup(do_enter_text).await
+        with_clipboard_backup(do_enter_text).await?
+    };
+
+    if let Some((app, text)) = entered {
+        let event_data = serde_json::json!({
+            "text": text,
+            "chars": text.chars().count()
+        });
+        app.emit("text-entered", event_data)?;
+    }
+
+    Ok(EnterTextResult {
+        preview: None,
+        inserted,
+    })
+}
+
+/// Wait until an application matching `app_glob` is frontmost with an editable field focused,
+/// then enter text like `enter_text`. Fails with a timeout error after `timeout_ms`.
+#[tauri::command]
+pub async fn enter_text_when_focused(
+    app: AppHandle,
+    text: String,
+    app_glob: String,
+    timeout_ms: u64,
+) -> Result<EnterTextResult, AppError> {
+    let deadline = Instant::now() + Duration::from_millis(timeout_ms);
+    loop {
+        let focused = platform::get_frontmost_app_id()
+            .is_some_and(|app_id| matches_wildcard(&app_glob, &app_id));
+        if focused && is_cursor_editable(&app).unwrap_or(false) {
+            break;
+        }
+
+        if Instant::now() >= deadline {
+            return Err(AppError::with_kind(
+                ErrorKind::Timeout,
+                format!("Timed out waiting for {} to be focused", app_glob),
+            ));
+        }
+        tokio::time::sleep(FOCUS_POLL_INTERVAL).await;
+    }
+
+    enter_text(app, text, None, None).await
+}
+
+/// Paste finished AI responses into the application remembered when the popup was shown.
+/// The popup emits `ai-request-done` when a response completes, and the response is only
+/// pasted if `autoPasteResponse` is enabled.
+pub(crate) fn listen_ai_request_done(app: &AppHandle) {
+    let app_handle = app.clone();
+    app.listen("ai-request-done", move |event| {
+        let Ok(AiRequestDone { text }) = serde_json::from_str(event.payload()) else {
+            warn!("Invalid ai-request-done payload: {}", event.payload());
+            return;
+        };
+
+        let app = app_handle.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = paste_ai_response(app, text).await {
+                warn!("Failed to paste AI response: {}", e);
+            }
+        });
+    });
+}
+
+/// Restore focus to the original application and paste the response into it.
+async fn paste_ai_response(app: AppHandle, text: String) -> Result<(), AppError> {
+    let store = app.store(SETTINGS_STORE)?;
+    let enabled = |key: &str| store.get(key).and_then(|v| v.as_bool()).unwrap_or(false);
+    if !enabled("autoPasteResponse") || text.is_empty() {
+        return Ok(());
+    }
+
+    if let Err(e) = restore_popup_focus(&app).await {
+        warn!("Failed to restore focus: {}", e);
+    }
+
+    enter_text(app.clone(), text, None, None).await?;
+    if !enabled("popupPinned") {
+        hide_popup(app)?;
+    }
+
+    Ok(())
+}
+
+/// Select the entered text, or the given original selection range again in insert modes.
+fn select_entered_text(
+    app: &AppHandle,
+    reselect: Option<(usize, usize)>,
+    chars: usize,
+) -> Result<(), AppError> {
+    if let Some((location, length)) = reselect {
+        return platform::set_selection_range(location, length);
+    }
+
+    // only select entered text if cursor position is editable
+    if !is_cursor_editable(app)? {
+        return Ok(());
+    }
+
+    // first try using native API to select text
+    if platform::select_backward_range(0, chars).is_err() {
+        // if native API call fails and char count is <= 50, use keyboard simulation
+        if chars <= 50 {
+            let mut enigo_guard = ENIGO.lock()?;
+            let enigo = enigo_guard.as_mut()?;
+            mark_synthetic_input();
+
+            enigo.key(Key::Shift, Direction::Press)?;
+            for _ in 0..chars {
+                #[cfg(target_os = "windows")]
+                std::thread::sleep(Duration::from_millis(5));
+
+                enigo.key(Key::LeftArrow, Direction::Click)?;
+            }
+            enigo.key(Key::Shift, Direction::Release)?;
+            mark_synthetic_input();
+        }
+    }
+
+    Ok(())
+}
+
+/// Replace the selection using native API, checking the focused text length afterwards since
+/// some applications (e.g. Chromium and Electron apps) accept the replacement and ignore it.
+/// Returns None if the selection was not replaced, otherwise whether it could be verified.
+fn replace_natively(text: &str) -> Option<Option<bool>> {
+    let check = LengthCheck::measure();
+    platform::replace_selection(text).ok()?;
+
+    match check.map(|check| check.is_entered(text)) {
+        Some(Some(false)) => {
+            warn!("Native replacement was not applied, falling back to paste");
+            None
+        }
+        Some(entered) => Some(entered),
+        None => Some(None),
+    }
+}
+
+// focused text length and selection length measured before entering text
+struct LengthCheck {
+    before: usize,
+    selected: usize,
+}
+
+impl LengthCheck {
+    /// Measure the focused text, returning None if its length is unavailable.
+    fn measure() -> Option<Self> {
+        let before = platform::get_focused_text_length().ok()?;
+        let selected = platform::get_selection_range().map_or(0, |(_, length)| length);
+        Some(LengthCheck { before, selected })
+    }
+
+    /// Check whether the focused text length changed by the entered length minus the
+    /// replaced selection, both in UTF-16 units. Line breaks are counted loosely since the
+    /// application may normalize them. Returns None if the length is unavailable.
+    fn is_entered(&self, text: &str) -> Option<bool> {
+        let after = platform::get_focused_text_length().ok()? as i64;
+        let expected = text.encode_utf16().count() as i64 - self.selected as i64;
+        let tolerance = text.matches('\n').count() as i64;
+        Some((after - self.before as i64 - expected).abs() <= tolerance)
+    }
+
+    /// Check whether nothing was selected and the focused text length did not change at all.
+    fn is_unchanged(&self) -> bool {
+        self.selected == 0
+            && platform::get_focused_text_length().is_ok_and(|after| after == self.before)
     }
 }
+
+/// Check if the cursor is in an editable field, honoring per-app `editableOverrides`.
+pub(crate) fn is_cursor_editable(app: &AppHandle) -> Result<bool, AppError> {
+    if get_editable_override(app) == Some(true) {
+        return Ok(true);
+    }
+    platform::is_cursor_editable()
+}
+
+/// Type text directly as Unicode input.
+/// Uses enigo's `text()` instead of per-character key events, so the result does not
+/// depend on the active keyboard layout.
+fn type_text(text: &str) -> Result<(), AppError> {
+    let mut enigo_guard = ENIGO.lock()?;
+    mark_synthetic_input();
+    enigo_guard.as_mut()?.text(text)?;
+    // typing may take a while, so extend the grace period past the last key
+    mark_synthetic_input();
+    Ok(())
+}
+
+/// Wrap selected text with prefix and suffix, leaving the wrapped text selected.
+/// Same as `enter_text` in wrap mode, so the selection is replaced natively on macOS when
+/// possible. Windows always pastes the wrapped text.
+#[tauri::command]
+pub async fn wrap_selection(
+    app: AppHandle,
+    prefix: String,
+    suffix: String,
+) -> Result<(), AppError> {
+    let options = EnterTextOptions {
+        mode: EnterMode::Wrap,
+        prefix: Some(prefix),
+        suffix: Some(suffix),
+        ..Default::default()
+    };
+    enter_text(app, String::new(), None, Some(options)).await?;
+
+    Ok(())
+}
+
+/// Insert text at the given UTF-16 offset in the focused field.
+/// Offsets beyond the field's text are clamped to its end. Inserts natively on macOS when
+/// possible, otherwise falls back to clipboard paste at the new cursor position.
+#[tauri::command]
+pub async fn insert_text_at(app: AppHandle, location: usize, text: String) -> Result<(), AppError> {
+    // fall back to end of field if location is out of range
+    let text_length = platform::get_focused_text_length()?;
+    let location = location.min(text_length);
+
+    // place cursor at location
+    platform::set_selection_range(location, 0)?;
+
+    // first try using native API to insert text
+    if platform::replace_selection(&text).is_ok() {
+        return Ok(());
+    }
+
+    // fall back to clipboard paste
+    enter_text(app, text, None, None).await?;
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use crate::error::AppError;
+use log::debug;
+use serde::Serialize;
+use tauri::AppHandle;
+use tauri_plugin_updater::UpdaterExt;
+
+// structure to hold update availability information
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateInfo {
+    pub current: String,
+    pub latest: Option<String>,
+    pub url: Option<String>,
+}
+
+/// Check whether a newer version is available from the configured updater endpoints.
+/// Only reports availability, never downloads anything.
+#[tauri::command]
+pub async fn check_for_update(app: AppHandle) -> Result<UpdateInfo, AppError> {
+    let current = app.package_info().version.to_string();
+    debug!("Checking for update from version {}", current);
+
+    let update = app.updater()?.check().await?;
+    Ok(UpdateInfo {
+        current,
+        latest: update.as_ref().map(|update| update.version.clone()),
+        url: update.map(|update| update.download_url.to_string()),
+    })
+}
@@ -1,11 +1,16 @@
-use crate::error::AppError;
+use crate::commands::perf::{record_latency, CURSOR_LOCATION};
+use crate::error::{AppError, ErrorKind};
 use crate::platform;
-use crate::{ENIGO, TOOLBAR_MENU_OPEN};
+use crate::{ENIGO, SETTINGS_STORE, TOOLBAR_MENU_OPEN};
 use enigo::Mouse;
+use log::warn;
 use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::time::Duration;
-use tauri::{AppHandle, Emitter, LogicalPosition, LogicalSize, Manager, Position, WebviewWindow};
+use std::time::{Duration, Instant};
+use tauri::{
+    AppHandle, Emitter, LogicalPosition, LogicalSize, Manager, Monitor, Position, WebviewWindow,
+};
+use tauri_plugin_store::StoreExt;
 
 // structure to hold window placement information
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -16,6 +21,19 @@ pub struct WindowPlacement {
     pub window_position: LogicalPosition<f64>,
 }
 
+// structure to hold the computed popup position for diagnostics
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PopupPositionPreview {
+    pub x: f64,
+    pub y: f64,
+    pub monitor: Option<String>,
+    pub centered: bool,
+}
+
+// minimum window opacity to keep windows visible
+const MIN_WINDOW_OPACITY: f64 = 0.1;
+
 // window position offset from cursor
 const WINDOW_OFFSET: i32 = 5;
 
@@ -25,6 +43,12 @@ const SAFE_AREA_BOTTOM: i32 = 80;
 // maximum wait time for window initialization
 const INITIALIZATION_TIMEOUT_MS: u64 = 5000;
 
+// maximum wait time for the main thread to restore focus
+const FOCUS_RESTORE_TIMEOUT: Duration = Duration::from_secs(1);
+
+// delay for the restored application to become active before sending keys to it
+const FOCUS_RESTORE_DELAY: Duration = Duration::from_millis(100);
+
 // initialization flags for popup and toolbar windows
 static POPUP_INITIALIZED: AtomicBool = AtomicBool::new(false);
 static TOOLBAR_INITIALIZED: AtomicBool = AtomicBool::new(false);
@@ -80,6 +104,8 @@ pub fn show_popup(app: AppHandle, payload: String, mouse: Option<bool>) -> Resul
     if let Some(window) = app.get_webview_window("popup") {
         // position window near cursor
         position_window_near_cursor(&window, mouse.unwrap_or(false))?;
+        apply_popup_opacity(&app, &window);
+        remember_focus_for_popup(&app);
 
         // show and focus window
         if !POPUP_INITIALIZED.load(Ordering::Relaxed) {
@@ -89,12 +115,49 @@ pub fn show_popup(app: AppHandle, payload: String, mouse: Option<bool>) -> Resul
         // wait for initialization and emit event
         wait_and_emit(&POPUP_INITIALIZED, window, payload);
     } else {
-        return Err("Popup window not found".into());
+        return Err(window_not_found("popup"));
     }
 
     Ok(())
 }
 
+/// Compute where the popup window would appear without showing it.
+/// Runs the same positioning math as `show_popup` to help diagnose multi-monitor setups.
+#[tauri::command]
+pub fn preview_popup_position(
+    app: AppHandle,
+    mouse: bool,
+) -> Result<PopupPositionPreview, AppError> {
+    let Some(window) = app.get_webview_window("popup") else {
+        return Err(window_not_found("popup"));
+    };
+
+    if let Some((position, monitor)) = compute_position_near_cursor(&window, mouse)? {
+        return Ok(PopupPositionPreview {
+            x: position.x,
+            y: position.y,
+            monitor: monitor.name().cloned(),
+            centered: false,
+        });
+    }
+
+    // window would be centered on its current monitor
+    let monitor = window
+        .current_monitor()?
+        .ok_or_else(|| AppError::from("No monitor found"))?;
+    let scale_factor = monitor.scale_factor();
+    let monitor_position = monitor.position().to_logical::<f64>(scale_factor);
+    let monitor_size = monitor.size().to_logical::<f64>(scale_factor);
+    let window_size = window.outer_size()?.to_logical::<f64>(scale_factor);
+
+    Ok(PopupPositionPreview {
+        x: monitor_position.x + (monitor_size.width - window_size.width) / 2.0,
+        y: monitor_position.y + (monitor_size.height - window_size.height) / 2.0,
+        monitor: monitor.name().cloned(),
+        centered: true,
+    })
+}
+
 /// Show popup window and position it at the given logical position.
 #[tauri::command]
 pub fn show_popup_sameplace(
@@ -137,6 +200,8 @@ pub fn show_popup_sameplace(
         };
 
         window.set_position(Position::Logical(position))?;
+        apply_popup_opacity(&app, &window);
+        remember_focus_for_popup(&app);
 
         // show and focus window
         if !POPUP_INITIALIZED.load(Ordering::Relaxed) {
@@ -146,7 +211,7 @@ pub fn show_popup_sameplace(
         // wait for initialization and emit event
         wait_and_emit(&POPUP_INITIALIZED, window, payload);
     } else {
-        return Err("Popup window not found".into());
+        return Err(window_not_found("popup"));
     }
 
     Ok(())
@@ -158,7 +223,7 @@ pub fn position_toolbar(app: AppHandle, mouse: Option<bool>) -> Result<(), AppEr
     if let Some(window) = app.get_webview_window("toolbar") {
         position_window_near_cursor(&window, mouse.unwrap_or(false))?;
     } else {
-        return Err("Toolbar window not found".into());
+        return Err(window_not_found("toolbar"));
     }
 
     Ok(())
@@ -179,7 +244,28 @@ pub fn show_toolbar(app: AppHandle, payload: String, mouse: Option<bool>) -> Res
         // wait for initialization and emit event
         wait_and_emit(&TOOLBAR_INITIALIZED, window, payload);
     } else {
-        return Err("Toolbar window not found".into());
+        return Err(window_not_found("toolbar"));
+    }
+
+    Ok(())
+}
+
+/// Show toolbar window and position it at the given logical coordinates.
+#[tauri::command]
+pub fn show_toolbar_at(app: AppHandle, payload: String, x: f64, y: f64) -> Result<(), AppError> {
+    if let Some(window) = app.get_webview_window("toolbar") {
+        // position window at coordinates
+        position_window_at(&window, x, y)?;
+
+        // show window without focusing
+        if !TOOLBAR_INITIALIZED.load(Ordering::Relaxed) {
+            show_toolbar_regardless(app.clone())?;
+        }
+
+        // wait for initialization and emit event
+        wait_and_emit(&TOOLBAR_INITIALIZED, window, payload);
+    } else {
+        return Err(window_not_found("toolbar"));
     }
 
     Ok(())
@@ -200,7 +286,8 @@ pub fn show_toolbar_regardless(app: AppHandle) -> Result<(), AppError> {
     #[cfg(not(target_os = "macos"))]
     {
         if let Some(window) = app.get_webview_window("toolbar") {
-            window.show()?;
+            // show without activating to keep focus in the underlying application
+            platform::show_window_noactivate(window.hwnd()?.0 as _);
             // refresh z-order to ensure toolbar stays on top
             window.set_always_on_top(false)?;
             window.set_always_on_top(true)?;
@@ -210,6 +297,95 @@ pub fn show_toolbar_regardless(app: AppHandle) -> Result<(), AppError> {
     Ok(())
 }
 
+/// Set window opacity, clamped to 0.1..=1.0.
+#[tauri::command]
+pub fn set_window_opacity(app: AppHandle, label: String, opacity: f64) -> Result<(), AppError> {
+    let window = app
+        .get_webview_window(&label)
+        .ok_or_else(|| window_not_found(&label))?;
+
+    apply_window_opacity(&window, opacity)
+}
+
+/// Hide popup window.
+#[tauri::command]
+pub fn hide_popup(app: AppHandle) -> Result<(), AppError> {
+    hide_floating_window(&app, "popup")
+}
+
+/// Hide toolbar window.
+#[tauri::command]
+pub fn hide_toolbar(app: AppHandle) -> Result<(), AppError> {
+    hide_floating_window(&app, "toolbar")
+}
+
+/// Check whether a window is currently visible.
+#[tauri::command]
+pub fn is_window_visible(app: AppHandle, label: String) -> Result<bool, AppError> {
+    // check panel visibility first on macOS
+    #[cfg(target_os = "macos")]
+    {
+        use tauri_nspanel::ManagerExt;
+
+        if let Ok(panel) = app.get_webview_panel(&label) {
+            return Ok(panel.is_visible());
+        }
+    }
+
+    let window = app
+        .get_webview_window(&label)
+        .ok_or_else(|| window_not_found(&label))?;
+
+    Ok(window.is_visible()?)
+}
+
+/// Set whether a window stays above other windows, including fullscreen ones.
+#[tauri::command]
+pub fn set_window_topmost(app: AppHandle, label: String, topmost: bool) -> Result<(), AppError> {
+    let window = app
+        .get_webview_window(&label)
+        .ok_or_else(|| window_not_found(&label))?;
+
+    // use native topmost z-order band on Windows
+    #[cfg(target_os = "windows")]
+    platform::set_window_topmost(window.hwnd()?.0 as _, topmost)?;
+
+    // panels keep the level set up for them, which already floats above fullscreen windows
+    // on macOS, and changing it would drop them to the normal window level
+    #[cfg(target_os = "macos")]
+    {
+        use tauri_nspanel::ManagerExt;
+
+        if app.get_webview_panel(&label).is_err() {
+            window.set_always_on_top(topmost)?;
+        }
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    window.set_always_on_top(topmost)?;
+
+    Ok(())
+}
+
+/// Get the raw mouse position in screen coordinates, as seen by the backend.
+#[tauri::command]
+pub fn get_mouse_position() -> Result<(i32, i32), AppError> {
+    get_mouse_location()
+}
+
+/// Remember the focused window and element, so `restore_focus` can return to it later.
+#[tauri::command]
+pub fn remember_focus() -> Result<(), AppError> {
+    platform::remember_focus()
+}
+
+/// Re-activate the window and (best effort) element saved by `remember_focus`.
+/// Returns false if no focus was remembered.
+#[tauri::command]
+pub fn restore_focus() -> Result<bool, AppError> {
+    platform::restore_focus()
+}
+
 /// Wait for window initialization and emit event.
 ///
 /// If already initialized, emit event immediately.
@@ -244,21 +420,50 @@ fn wait_and_emit(flag: &'static AtomicBool, window: WebviewWindow, payload: Stri
 
 /// Position a window near the mouse or selection with safe area constraints.
 fn position_window_near_cursor(window: &WebviewWindow, mouse: bool) -> Result<(), AppError> {
+    match compute_position_near_cursor(window, mouse)? {
+        Some((position, _)) => window.set_position(Position::Logical(position))?,
+        None => window.center()?,
+    }
+    Ok(())
+}
+
+/// Compute the logical position of a window near the mouse or selection, together with
+/// the monitor it lands on. Returns None if the window should be centered instead.
+fn compute_position_near_cursor(
+    window: &WebviewWindow,
+    mouse: bool,
+) -> Result<Option<(LogicalPosition<f64>, Monitor)>, AppError> {
     // get cursor position (may be physical or logical depending on platform)
     let mut mouse_position = true;
 
-    #[allow(unused_mut)]
-    let (mut x, mut y) = if mouse {
+    let location = if mouse {
         // directly use mouse position from enigo
-        ENIGO.lock()?.as_ref()?.location()?
+        get_mouse_location()
     } else {
         // try to get selection location first, fall back to mouse position if failed
-        match platform::get_cursor_location() {
+        let start = Instant::now();
+        let location = platform::get_cursor_location();
+        record_latency(CURSOR_LOCATION, start);
+
+        match location {
             Ok(location) => {
                 mouse_position = false;
-                location
+                Ok(location)
             }
-            Err(_) => ENIGO.lock()?.as_ref()?.location()?,
+            Err(_) => get_mouse_location(),
+        }
+    };
+
+    // center window on current monitor if no position is available
+    #[allow(unused_mut)]
+    let (mut x, mut y) = match location {
+        Ok(location) => location,
+        Err(error) => {
+            warn!(
+                "Cursor-based positioning failed, centering window: {}",
+                error
+            );
+            return Ok(None);
         }
     };
 
@@ -331,9 +536,159 @@ fn position_window_near_cursor(window: &WebviewWindow, mouse: bool) -> Result<()
     } else {
         -WINDOW_OFFSET
     };
-    window.set_position(Position::Logical(LogicalPosition {
+    let position = LogicalPosition {
         x: (x + window_offset).clamp(min_x, max_x) as f64,
         y: (y + window_offset).clamp(min_y, max_y) as f64,
+    };
+
+    Ok(Some((position, monitor)))
+}
+
+/// Apply window opacity with the platform API.
+fn apply_window_opacity(window: &WebviewWindow, opacity: f64) -> Result<(), AppError> {
+    let opacity = if opacity.is_finite() {
+        opacity.clamp(MIN_WINDOW_OPACITY, 1.0)
+    } else {
+        1.0
+    };
+
+    #[cfg(target_os = "macos")]
+    platform::set_window_opacity(window.ns_window()?, opacity)?;
+
+    #[cfg(not(target_os = "macos"))]
+    platform::set_window_opacity(window.hwnd()?.0 as _, opacity)?;
+
+    Ok(())
+}
+
+/// Apply the persisted popup opacity setting.
+fn apply_popup_opacity(app: &AppHandle, window: &WebviewWindow) {
+    let opacity = app
+        .store(SETTINGS_STORE)
+        .ok()
+        .and_then(|store| store.get("popupOpacity"))
+        .and_then(|v| v.as_f64());
+    if let Some(opacity) = opacity {
+        let _ = apply_window_opacity(window, opacity);
+    }
+}
+
+/// Remember the focused application before showing popup, so responses can be pasted back
+/// automatically or while the popup is kept open.
+fn remember_focus_for_popup(app: &AppHandle) {
+    let Ok(store) = app.store(SETTINGS_STORE) else {
+        return;
+    };
+    let enabled = |key: &str| store.get(key).and_then(|v| v.as_bool()).unwrap_or(false);
+    if enabled("autoPasteResponse") || enabled("keepPopupOpen") {
+        if let Err(e) = platform::remember_focus() {
+            warn!("Failed to remember focus: {}", e);
+        }
+    }
+}
+
+/// Hide a floating window and emit its hide event.
+fn hide_floating_window(app: &AppHandle, label: &str) -> Result<(), AppError> {
+    // give up key status of panel on macOS
+    #[cfg(target_os = "macos")]
+    {
+        use tauri_nspanel::ManagerExt;
+
+        if let Ok(panel) = app.get_webview_panel(label) {
+            panel.resign_key_window();
+        }
+    }
+
+    if hide_window(app, label).is_none() {
+        return Err(window_not_found(label));
+    }
+
+    // emit window hide event
+    app.emit(&format!("hide-{}", label), ())?;
+
+    Ok(())
+}
+
+/// Give keyboard focus back from the popup to the application and element remembered when
+/// it was shown, waiting for the main thread so keys sent afterwards land there.
+pub(crate) async fn restore_popup_focus(app: &AppHandle) -> Result<(), AppError> {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    let app_handle = app.clone();
+    app.run_on_main_thread(move || {
+        // give up key status of popup panel on macOS
+        #[cfg(target_os = "macos")]
+        {
+            use tauri_nspanel::ManagerExt;
+
+            if let Ok(panel) = app_handle.get_webview_panel("popup") {
+                panel.resign_key_window();
+            }
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        let _ = app_handle;
+
+        let _ = tx.send(platform::restore_focus());
+    })?;
+
+    // avoid hanging forever if main thread is stuck
+    let restored = tokio::time::timeout(FOCUS_RESTORE_TIMEOUT, rx)
+        .await
+        .map_err(|_| {
+            AppError::with_kind(ErrorKind::Timeout, "Timed out waiting for main thread")
+        })???;
+
+    // wait for the restored application to become active
+    if restored {
+        tokio::time::sleep(FOCUS_RESTORE_DELAY).await;
+    }
+
+    Ok(())
+}
+
+/// Create a not found error for the given window label.
+#[track_caller]
+fn window_not_found(label: &str) -> AppError {
+    AppError::with_kind(ErrorKind::NotFound, format!("Window {} not found", label))
+}
+
+/// Get current mouse position from enigo.
+fn get_mouse_location() -> Result<(i32, i32), AppError> {
+    Ok(ENIGO.lock()?.as_ref()?.location()?)
+}
+
+/// Position a window at the given logical coordinates with safe area constraints.
+fn position_window_at(window: &WebviewWindow, x: f64, y: f64) -> Result<(), AppError> {
+    // get monitor containing the coordinates
+    let monitor = window
+        .available_monitors()?
+        .into_iter()
+        .find(|m| {
+            let scale = m.scale_factor();
+            let pos = m.position().to_logical::<f64>(scale);
+            let size = m.size().to_logical::<f64>(scale);
+            x >= pos.x && x < pos.x + size.width && y >= pos.y && y < pos.y + size.height
+        })
+        .or_else(|| window.current_monitor().ok().flatten())
+        .ok_or_else(|| AppError::from("No monitor found"))?;
+
+    let scale_factor = monitor.scale_factor();
+    let screen_size = monitor.size().to_logical::<f64>(scale_factor);
+    let screen_position = monitor.position().to_logical::<f64>(scale_factor);
+    let window_size = window.outer_size()?.to_logical::<f64>(scale_factor);
+
+    // calculate safe area for window
+    let safe_area_bottom = SAFE_AREA_BOTTOM as f64 / scale_factor;
+    let min_x = screen_position.x;
+    let max_x = (screen_position.x + screen_size.width - window_size.width).max(min_x);
+    let min_y = screen_position.y;
+    let max_y =
+        (screen_position.y + screen_size.height - window_size.height - safe_area_bottom).max(min_y);
+
+    // clamp window position to safe area
+    window.set_position(Position::Logical(LogicalPosition {
+        x: x.clamp(min_x, max_x),
+        y: y.clamp(min_y, max_y),
     }))?;
 
     Ok(())
@@ -1,24 +1,28 @@
 use log::error;
 
-#[derive(Debug, Clone)]
-pub struct AppError(String);
+// error category, so the frontend can branch on the kind of failure
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize)]
+pub enum ErrorKind {
+    PermissionDenied,
+    RuntimeMissing,
+    NotFound,
+    Timeout,
+    #[default]
+    Other,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct AppError {
+    kind: ErrorKind,
+    message: String,
+}
 
 impl std::error::Error for AppError {}
 
 // implement Display, so error messages can be printed directly
 impl std::fmt::Display for AppError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
-    }
-}
-
-// implement Serialize, so it can be used as a Tauri command return type
-impl serde::Serialize for AppError {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::ser::Serializer,
-    {
-        serializer.serialize_str(&self.0)
+        write!(f, "{}", self.message)
     }
 }
 
@@ -26,10 +30,16 @@ impl serde::Serialize for AppError {
 impl AppError {
     #[track_caller]
     fn new(msg: impl Into<String>) -> Self {
-        let msg = msg.into();
+        AppError::with_kind(ErrorKind::Other, msg)
+    }
+
+    /// Create an error of the given kind.
+    #[track_caller]
+    pub fn with_kind(kind: ErrorKind, msg: impl Into<String>) -> Self {
+        let message = msg.into();
         let location = std::panic::Location::caller();
-        error!("[{}:{}] {}", location.file(), location.line(), msg);
-        AppError(msg)
+        error!("[{}:{}] {}", location.file(), location.line(), message);
+        AppError { kind, message }
     }
 }
 
@@ -83,13 +93,18 @@ macro_rules! impl_from_error {
 impl_from_error!(
     std::io::Error,
     std::sync::mpsc::RecvError,
+    std::sync::mpsc::RecvTimeoutError,
+    tokio::sync::oneshot::error::RecvError,
     serde_json::error::Error,
     tauri::Error,
     tauri_plugin_store::Error,
     tauri_plugin_global_shortcut::Error,
+    tauri_plugin_opener::Error,
     enigo::InputError,
     &enigo::NewConError,
     &mut enigo::NewConError,
+    csv::Error,
+    tauri_plugin_updater::Error,
     Box<dyn std::error::Error + Send + Sync>,
 );
 impl_from_error!(generic: std::sync::PoisonError<T>);
@@ -1,5 +1,7 @@
 mod keyboard;
 mod mouse;
+mod power;
 
 pub use keyboard::handle_keyboard_event;
 pub use mouse::handle_mouse_event;
+pub use power::handle_system_wake;
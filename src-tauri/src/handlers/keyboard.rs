@@ -1,4 +1,6 @@
-use crate::commands::{get_selection, is_blocked};
+use crate::commands::{
+    capture_selection_now, get_selection, is_blocked, is_capture_hotkey, take_suppressed_capture,
+};
 use crate::{REGISTERED_SHORTCUTS, SHORTCUT_PAUSED, SHORTCUT_SUSPEND};
 use std::sync::atomic::Ordering;
 use tauri::{AppHandle, Emitter};
@@ -18,6 +20,20 @@ pub fn handle_keyboard_event(app: &AppHandle, hotkey: &Shortcut, event: Shortcut
 
     // only handle key release events
     if event.state() == ShortcutState::Released {
+        // skip this capture if it was suppressed
+        if take_suppressed_capture() {
+            return;
+        }
+
+        // capture hotkey emits the selection like a drag-end gesture
+        if is_capture_hotkey(hotkey) {
+            let app_handle = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let _ = capture_selection_now(app_handle).await;
+            });
+            return;
+        }
+
         // get shortcut string from registered shortcuts
         let shortcut = REGISTERED_SHORTCUTS
             .lock()
@@ -28,10 +44,11 @@ pub fn handle_keyboard_event(app: &AppHandle, hotkey: &Shortcut, event: Shortcut
         // emit shortcut event with selection
         let app_handle = app.clone();
         tauri::async_runtime::spawn(async move {
-            if let Ok(selection) = get_selection(app_handle.clone(), Some(false)).await {
+            if let Ok(selection) = get_selection(app_handle.clone(), Some(false), None).await {
                 let event_data = serde_json::json!({
                     "shortcut": shortcut,
-                    "selection": selection
+                    "selection": selection.text,
+                    "truncated": selection.truncated
                 });
                 let _ = app_handle.emit("shortcut", event_data);
             }
@@ -1,8 +1,11 @@
-use crate::commands::{get_selection, is_blocked};
+use crate::commands::{
+    get_selection, hide_popup, is_blocked, is_synthetic_input, is_window_visible,
+    mark_synthetic_input, take_suppressed_capture,
+};
 use crate::error::AppError;
 use crate::platform;
 use crate::{
-    APP_HANDLE, ENIGO, IBEAM_CURSOR, LONG_PRESS, LONG_PRESS_DURATION, SHORTCUT_PAUSED,
+    APP_HANDLE, DISMISS_KEY, ENIGO, IBEAM_CURSOR, LONG_PRESS, LONG_PRESS_DURATION, SHORTCUT_PAUSED,
     SHORTCUT_SUSPEND, TOOLBAR_MENU_OPEN,
 };
 use enigo::{Direction, Key as EnigoKey, Keyboard, Mouse};
@@ -122,11 +125,19 @@ pub fn handle_mouse_event(event: Event) {
                 SHIFT_PRESSED.set(true);
             }
 
+            // ignore our own injected keystrokes, e.g. a paste right after showing the toolbar
+            if is_synthetic_input() {
+                return;
+            }
+
             // close native action menu on key press
             if matches!(close_native_menu(key, event.platform_code), Ok(true)) {
                 return;
             }
 
+            // hide popup on dismiss key press
+            let _ = handle_dismiss_key(key);
+
             // hide toolbar on key press
             let _ = hide_toolbar(false);
         }
@@ -135,7 +146,9 @@ pub fn handle_mouse_event(event: Event) {
         }
         EventType::Wheel { .. } => {
             // hide toolbar on wheel scroll
-            let _ = hide_toolbar(false);
+            if !is_synthetic_input() {
+                let _ = hide_toolbar(false);
+            }
         }
         _ => (),
     }
@@ -186,6 +199,32 @@ fn detect_user_copy_operation(event_type: EventType) {
     }
 }
 
+/// Hide popup window if the configured dismiss key is pressed.
+fn handle_dismiss_key(key: Key) -> Result<(), AppError> {
+    // rdev key names match `KeyboardEvent.code` for most keys (e.g. Escape, F1, KeyQ)
+    {
+        let dismiss_key = DISMISS_KEY.lock()?;
+        if dismiss_key.is_empty() || format!("{:?}", key) != *dismiss_key {
+            return Ok(());
+        }
+    }
+
+    let app = APP_HANDLE
+        .lock()?
+        .clone()
+        .ok_or("App handle not available")?;
+
+    // window operations must run on main thread
+    let app_handle = app.clone();
+    app.run_on_main_thread(move || {
+        if is_window_visible(app_handle.clone(), "popup".into()).unwrap_or(false) {
+            let _ = hide_popup(app_handle);
+        }
+    })?;
+
+    Ok(())
+}
+
 /// Update platform copy modifier state (Ctrl on Windows, Command on macOS).
 fn update_copy_modifier_state(key: Key, pressed: bool) {
     #[cfg(target_os = "windows")]
@@ -356,6 +395,11 @@ fn emit_event(shortcut: &str, with_selection: Option<bool>) -> Result<(), AppErr
             return Ok(());
         }
 
+        // skip this capture if it was suppressed
+        if take_suppressed_capture() {
+            return Ok(());
+        }
+
         // emit event directly without fetching selection
         if !with_selection.unwrap_or(false) {
             let event_data = serde_json::json!({
@@ -370,12 +414,13 @@ fn emit_event(shortcut: &str, with_selection: Option<bool>) -> Result<(), AppErr
         let app_handle = app.clone();
         let shortcut = shortcut.to_string();
         tauri::async_runtime::spawn(async move {
-            if let Ok(selection) = get_selection(app_handle.clone(), Some(true)).await {
-                if !selection.trim().is_empty() {
+            if let Ok(selection) = get_selection(app_handle.clone(), Some(true), None).await {
+                if !selection.text.trim().is_empty() {
                     // emit event if selection is not empty
                     let event_data = serde_json::json!({
                         "shortcut": shortcut,
-                        "selection": selection
+                        "selection": selection.text,
+                        "truncated": selection.truncated
                     });
                     let _ = app_handle.emit("shortcut", event_data);
                 }
@@ -429,6 +474,7 @@ fn close_native_menu(key: Key, platform_code: u32) -> Result<bool, AppError> {
             let escape_result = (|| -> Result<(), AppError> {
                 let mut enigo_guard = ENIGO.lock()?;
                 let enigo = enigo_guard.as_mut()?;
+                mark_synthetic_input();
                 Ok(enigo.key(EnigoKey::Escape, Direction::Click)?)
             })();
             SHORTCUT_SUSPEND.store(was_suspended, Ordering::Relaxed);
@@ -0,0 +1,43 @@
+use crate::commands::{rearm_capture_hotkey, register_shortcut, unregister_shortcut};
+use crate::{spawn_input_listener, APP_HANDLE, REGISTERED_SHORTCUTS};
+use log::{info, warn};
+
+/// Handle system wake event by re-arming shortcuts and listeners.
+pub fn handle_system_wake() {
+    // avoid blocking the power notification thread
+    std::thread::spawn(|| {
+        info!("System woke from sleep, re-arming shortcuts and listeners");
+
+        let Some(app) = APP_HANDLE.lock().ok().and_then(|h| h.clone()) else {
+            warn!("App handle is not available, skipping wake handling");
+            return;
+        };
+
+        // re-register global shortcuts
+        let shortcuts: Vec<String> = REGISTERED_SHORTCUTS
+            .lock()
+            .map(|r| r.values().cloned().collect())
+            .unwrap_or_default();
+        for shortcut in &shortcuts {
+            let _ = unregister_shortcut(app.clone(), shortcut.clone());
+            if let Err(error) = register_shortcut(app.clone(), shortcut.clone()) {
+                warn!("Failed to re-register shortcut {}: {}", shortcut, error);
+            }
+        }
+        info!("Re-registered {} shortcuts", shortcuts.len());
+
+        // re-register capture hotkey, which is not tracked with the other shortcuts
+        match rearm_capture_hotkey(&app) {
+            Ok(true) => info!("Re-registered capture hotkey"),
+            Ok(false) => {}
+            Err(error) => warn!("Failed to re-register capture hotkey: {}", error),
+        }
+
+        // restart mouse event listener if it has stopped
+        if spawn_input_listener() {
+            info!("Mouse event listener was stopped and has been restarted");
+        } else {
+            info!("Mouse event listener is alive");
+        }
+    });
+}
@@ -7,15 +7,17 @@ use clipboard_rs::ClipboardContext;
 use commands::*;
 use enigo::{Enigo, Settings};
 use fern::colors::ColoredLevelConfig;
-use handlers::{handle_keyboard_event, handle_mouse_event};
+use handlers::{handle_keyboard_event, handle_mouse_event, handle_system_wake};
 use log::LevelFilter;
-use rdev::listen;
+use rdev::{listen, Event};
 use std::collections::HashMap;
-use std::sync::atomic::{AtomicBool, AtomicU64};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{LazyLock, Mutex};
 use std::time::Instant;
 use tauri::{App, AppHandle, Emitter, Manager, RunEvent, WebviewWindow, WindowEvent};
 use tauri_plugin_deep_link::DeepLinkExt;
+use tauri_plugin_global_shortcut::Shortcut;
 use tauri_plugin_log::{Target, TargetKind};
 use tauri_plugin_store::StoreExt;
 
@@ -28,9 +30,18 @@ pub static APP_HANDLE: LazyLock<Mutex<Option<AppHandle>>> = LazyLock::new(|| Mut
 // global shortcut paused state
 pub static SHORTCUT_PAUSED: AtomicBool = AtomicBool::new(false);
 
+// global shortcut paused state set by do-not-disturb schedule
+pub static DND_PAUSED: AtomicBool = AtomicBool::new(false);
+
 // global shortcut suspend state
 pub static SHORTCUT_SUSPEND: AtomicBool = AtomicBool::new(false);
 
+// global time until which input events are treated as self-injected
+pub static SYNTHETIC_INPUT_UNTIL: Mutex<Option<Instant>> = Mutex::new(None);
+
+// global one-shot flag to skip the next selection capture
+pub static SUPPRESS_NEXT_CAPTURE: AtomicBool = AtomicBool::new(false);
+
 // global I-beam cursor check state
 pub static IBEAM_CURSOR: AtomicBool = AtomicBool::new(true);
 
@@ -40,9 +51,21 @@ pub static LONG_PRESS: AtomicBool = AtomicBool::new(false);
 // global long press duration threshold
 pub static LONG_PRESS_DURATION: AtomicU64 = AtomicU64::new(2000);
 
+// global release modifiers before simulated shortcuts state
+pub static RELEASE_MODIFIERS: AtomicBool = AtomicBool::new(true);
+
+// global key that dismisses the popup and toolbar (empty to disable)
+pub static DISMISS_KEY: LazyLock<Mutex<String>> = LazyLock::new(|| Mutex::new("Escape".into()));
+
+// global mouse event listener running state
+pub static INPUT_LISTENER_RUNNING: AtomicBool = AtomicBool::new(false);
+
 // global toolbar native menu open state
 pub static TOOLBAR_MENU_OPEN: AtomicBool = AtomicBool::new(false);
 
+// global capture hotkey that works without input monitoring permission
+pub static CAPTURE_HOTKEY: Mutex<Option<Shortcut>> = Mutex::new(None);
+
 // global registered shortcuts mapping
 pub static REGISTERED_SHORTCUTS: LazyLock<Mutex<HashMap<u32, String>>> =
     LazyLock::new(|| Mutex::new(HashMap::new()));
@@ -62,6 +85,21 @@ pub static CLIPBOARD_RESTORE_INTERRUPTED: AtomicBool = AtomicBool::new(false);
 pub static SELECTION_TEXT_CACHE: LazyLock<Mutex<Option<(String, Instant)>>> =
     LazyLock::new(|| Mutex::new(None));
 
+// global last captured selection with timestamp
+pub static LAST_SELECTION: LazyLock<Mutex<Option<(LastSelection, Instant)>>> =
+    LazyLock::new(|| Mutex::new(None));
+
+// global last captured selection expiry in seconds
+pub static LAST_SELECTION_EXPIRY: AtomicU64 = AtomicU64::new(300);
+
+// global loaded plugin manifests
+pub static PLUGINS: LazyLock<Mutex<Option<Vec<PluginManifest>>>> =
+    LazyLock::new(|| Mutex::new(None));
+
+// global latency samples for performance metrics
+pub static PERF_SAMPLES: LazyLock<Mutex<HashMap<&'static str, PerfSamples>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
 #[cfg(target_os = "macos")]
 use tauri_nspanel::{
     tauri_panel, CollectionBehavior, ManagerExt, PanelLevel, StyleMask, TrackingAreaOptions,
@@ -104,13 +142,13 @@ tauri_panel! {
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    // register single instance plugin
+    // register single instance plugin first so a second launch exits before
+    // registering global shortcuts or starting the input listener
     #[allow(unused_mut)]
     let mut builder =
         tauri::Builder::default().plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
-            if let Some(window) = app.get_webview_window("main") {
-                let _ = window.set_focus();
-            }
+            // show main window of the running instance instead
+            show_main_window(app.clone());
         }));
 
     // register nspanel plugin on macOS
@@ -163,44 +201,155 @@ pub fn run() {
             set_toolbar_menu_open,
             show_popup,
             show_popup_sameplace,
+            preview_popup_position,
             position_toolbar,
             show_toolbar,
+            show_toolbar_at,
             show_toolbar_regardless,
+            hide_popup,
+            hide_toolbar,
+            is_window_visible,
+            get_mouse_position,
+            remember_focus,
+            restore_focus,
+            set_window_opacity,
+            set_window_topmost,
             navigate_to,
             register_shortcut,
             unregister_shortcut,
             is_shortcut_registered,
+            set_capture_hotkey,
             pause_shortcut_handling,
             resume_shortcut_handling,
+            suppress_next_capture,
             set_long_press_enabled,
             set_long_press_duration,
             set_ibeam_cursor_enabled,
+            set_ibeam_hotspots,
+            set_release_modifiers_enabled,
+            set_dismiss_key,
             get_selection,
+            get_selection_with_method,
+            get_selection_range,
+            set_selection_range,
+            expand_selection,
+            calibrate_selection,
+            dump_focused_tree,
+            get_last_selection,
+            capture_selection_now,
+            set_last_selection_expiry,
             get_clipboard_text,
             set_clipboard_text,
+            get_clipboard_formats,
             clear_clipboard,
             execute_python,
             execute_javascript,
             execute_shell,
             execute_powershell,
             enter_text,
+            enter_text_when_focused,
+            wrap_selection,
+            insert_text_at,
+            selftest_roundtrip,
             send_cut_keys,
             send_copy_keys,
             send_paste_keys,
             setup_tray,
             show_about,
+            set_locale,
+            set_tray_left_click_action,
+            get_system_info,
             check_accessibility,
             open_accessibility,
             check_input_monitoring,
             open_input_monitoring,
+            enable_accessibility_for_frontmost,
+            set_axapi_cache_duration,
             get_app_id,
-            is_blocked
+            app_ids_equal,
+            is_blocked,
+            validate_rule,
+            test_rule_now,
+            is_terminal_frontmost,
+            export_config,
+            import_config,
+            convert_width,
+            convert_chinese,
+            to_pinyin,
+            slugify,
+            markdown_to_text,
+            sanitize_html,
+            diff_text,
+            process_lines,
+            extract_column,
+            format_timestamp,
+            convert_number,
+            sanitize_text,
+            convert_line_endings,
+            detect_line_endings,
+            reflow_text,
+            change_indent,
+            toggle_comment,
+            escape_string,
+            unescape_string,
+            regex_matches,
+            generate_qr,
+            render_selection_image,
+            list_plugins,
+            reload_plugins,
+            run_plugin,
+            open_selection,
+            web_search,
+            save_output,
+            append_to_note,
+            get_perf_metrics,
+            check_for_update,
+            restart_listeners,
+            is_mouse_listener_alive,
+            get_listener_status
         ])
         .build(tauri::generate_context!())
         .expect("error while running tauri application")
         .run(handle_run_event);
 }
 
+/// Start mouse event listener thread if it is not already running.
+/// Returns true if a new listener thread was spawned.
+pub(crate) fn spawn_input_listener() -> bool {
+    if INPUT_LISTENER_RUNNING.swap(true, Ordering::SeqCst) {
+        return false;
+    }
+
+    let spawned = std::thread::Builder::new()
+        .name("mouse-listener".into())
+        .spawn(|| {
+            // listen blocks for the lifetime of the listener, so returning means it stopped
+            if let Err(error) = listen(handle_mouse_event_catching_panics) {
+                log::error!("Error starting mouse event listener: {:?}", error);
+            }
+            INPUT_LISTENER_RUNNING.store(false, Ordering::SeqCst);
+        });
+    if let Err(error) = spawned {
+        log::error!("Error spawning mouse event listener thread: {}", error);
+        INPUT_LISTENER_RUNNING.store(false, Ordering::SeqCst);
+        return false;
+    }
+
+    true
+}
+
+/// Handle mouse event and log panics instead of letting them kill the listener thread.
+fn handle_mouse_event_catching_panics(event: Event) {
+    if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| handle_mouse_event(event))) {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        log::error!("Mouse event handler panicked: {}", message);
+    }
+}
+
 /// Application setup function.
 fn setup_app(app: &mut App) -> Result<(), Box<dyn std::error::Error>> {
     let app_handle = app.app_handle().clone();
@@ -210,16 +359,34 @@ fn setup_app(app: &mut App) -> Result<(), Box<dyn std::error::Error>> {
         *handle = Some(app_handle.clone());
     }
 
+    // upgrade settings store from older versions
+    if let Err(error) = migrate_settings(&app_handle) {
+        log::error!("Error migrating settings: {}", error);
+    }
+
     // start mouse event listener
     // https://github.com/Narsil/rdev/issues/165
     #[cfg(target_os = "macos")]
     rdev::set_is_main_thread(false);
 
-    std::thread::spawn(|| {
-        if let Err(error) = listen(handle_mouse_event) {
-            log::error!("Error starting mouse event listener: {:?}", error);
-        }
-    });
+    // wait for input monitoring permission before listening
+    spawn_input_listener_when_permitted(app_handle.clone());
+
+    // paste finished AI responses back when auto-paste is enabled
+    listen_ai_request_done(&app_handle);
+
+    // load user plugins from plugin directory
+    if let Err(error) = reload_plugins(app_handle.clone()) {
+        log::error!("Error loading plugins: {}", error);
+    }
+
+    // pause and resume shortcut handling on schedule
+    start_dnd_scheduler(app_handle.clone());
+
+    // re-arm shortcuts and listeners after system wake
+    if let Err(error) = platform::register_wake_listener(handle_system_wake) {
+        log::error!("Error registering system wake listener: {}", error);
+    }
 
     // setup system tray
     setup_tray(
@@ -297,6 +464,12 @@ fn setup_app(app: &mut App) -> Result<(), Box<dyn std::error::Error>> {
                 }
             }
 
+            // prevent toolbar from stealing focus on Windows
+            #[cfg(target_os = "windows")]
+            if let Ok(hwnd) = window.hwnd() {
+                crate::platform::set_window_noactivate(hwnd.0 as _);
+            }
+
             // prevent position deviation on first show
             let _ = window.set_size(tauri::Size::Logical(tauri::LogicalSize {
                 width: 1.0,
@@ -5,11 +5,19 @@ mod windows;
 
 #[cfg(target_os = "macos")]
 pub use macos::{
-    get_app_id, get_cursor_location, get_frontmost_app_id, get_frontmost_url, get_selection,
-    is_cursor_editable, is_ibeam_cursor, select_backward_chars,
+    boost_thread_priority, dump_focused_tree, enable_accessibility_for_frontmost, get_app_id,
+    get_cursor_location, get_focused_text, get_focused_text_length, get_frontmost_app_id,
+    get_frontmost_url, get_frontmost_window_title, get_held_modifiers, get_selection,
+    get_selection_range, is_cursor_editable, is_ibeam_cursor, register_wake_listener,
+    remember_focus, replace_selection, reset_axapi_cache, restore_focus, select_backward_range,
+    set_axapi_cache_duration, set_ibeam_hotspots, set_selection_range, set_window_opacity,
 };
 #[cfg(target_os = "windows")]
 pub use windows::{
-    get_app_id, get_cursor_location, get_frontmost_app_id, get_frontmost_url, get_selection,
-    is_cursor_editable, is_ibeam_cursor, select_backward_chars,
+    boost_thread_priority, dump_focused_tree, get_app_id, get_cursor_location, get_focused_text,
+    get_focused_text_length, get_frontmost_app_id, get_frontmost_url, get_frontmost_window_title,
+    get_held_modifiers, get_selection, get_selection_range, is_cursor_editable, is_ibeam_cursor,
+    register_wake_listener, remember_focus, replace_selection, restore_focus,
+    select_backward_range, set_selection_range, set_window_noactivate, set_window_opacity,
+    set_window_topmost, show_window_noactivate,
 };
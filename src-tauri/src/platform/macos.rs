@@ -1,13 +1,18 @@
-use crate::error::AppError;
+use crate::error::{AppError, ErrorKind};
 use core_foundation::array::CFArray;
 use core_foundation::base::{CFRange, CFType, CFTypeRef, TCFType};
+use core_foundation::number::CFNumber;
+use core_foundation::runloop::{
+    kCFRunLoopDefaultMode, CFRunLoop, CFRunLoopSource, CFRunLoopSourceRef,
+};
 use core_foundation::string::{CFString, CFStringRef};
 use plist::Value;
 use std::collections::HashMap;
 use std::os::raw::c_void;
 use std::path::Path;
-use std::sync::Mutex;
-use std::time::Instant;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
 // bounds validation constants
 const MIN_VALID_WIDTH: f64 = 1.0;
@@ -17,16 +22,48 @@ const MAX_VALID_COORDINATE: f64 = 10000.0;
 // editable accessibility roles
 const EDITABLE_AX_ROLES: &[&str] = &["AXTextField", "AXTextArea", "AXComboBox"];
 
+// known I-Beam cursor hotSpots and matching tolerance in points
+const DEFAULT_IBEAM_HOTSPOTS: &[(f64, f64)] = &[(4.0, 9.0), (16.0, 16.0), (12.0, 11.0)];
+const IBEAM_HOTSPOT_TOLERANCE: f64 = 2.0;
+
+// user-provided I-Beam cursor hotSpots in addition to the known ones
+static EXTRA_IBEAM_HOTSPOTS: Mutex<Vec<(f64, f64)>> = Mutex::new(Vec::new());
+
+// virtual key codes of left/right modifier keys in Meta, Control, Alt, Shift order
+const MODIFIER_KEY_CODES: [(u16, u16); 4] = [(55, 54), (59, 62), (58, 61), (56, 60)];
+const CG_EVENT_SOURCE_STATE_HID_SYSTEM_STATE: i32 = 1;
+
 // AXValueType enumerations
 // https://developer.apple.com/documentation/applicationservices/axvaluetype
 const AX_VALUE_TYPE_CG_RECT: i32 = 3;
 const AX_VALUE_TYPE_CF_RANGE: i32 = 4;
 
 // track PIDs with their last processed time to avoid redundant AXAPI setup
-// each PID entry is valid for 5 seconds, after which it's considered a new process
-const PID_CACHE_EXPIRE_SECS: u64 = 5;
+// each PID entry is valid for 5 seconds by default, after which it's considered a new process
+// and the AXAPI attributes are set again on the next selection attempt
+// manual enabling via `enable_accessibility_for_frontmost` ignores this cache
+// setting the duration to 0 disables the cache entirely
+static PID_CACHE_EXPIRE_SECS: AtomicU64 = AtomicU64::new(5);
 static PROCESSED_PIDS: Mutex<Option<HashMap<i32, Instant>>> = Mutex::new(None);
 
+// cached frontmost app id keyed by process ID, with lookup time
+static APP_ID_CACHE: Mutex<Option<(i32, Instant, Option<String>)>> = Mutex::new(None);
+const APP_ID_CACHE_TTL: Duration = Duration::from_millis(500);
+
+// application and focused element remembered by `remember_focus`
+struct RememberedFocus {
+    pid: i32,
+    element: Option<CFType>,
+}
+
+// AXUIElement references are immutable and can be used from any thread
+unsafe impl Send for RememberedFocus {}
+
+static REMEMBERED_FOCUS: Mutex<Option<RememberedFocus>> = Mutex::new(None);
+
+// NSApplicationActivationOptions: activate ignoring other apps
+const NS_APPLICATION_ACTIVATE_IGNORING_OTHER_APPS: u64 = 1 << 1;
+
 // NSPoint structure for macOS AppKit
 #[repr(C)]
 #[derive(Clone, Copy)]
@@ -85,6 +122,27 @@ unsafe extern "C" {
     unsafe fn AXValueCreate(value_type: i32, value_ptr: *const c_void) -> CFTypeRef;
 
     unsafe fn AXValueGetValue(value: CFTypeRef, value_type: i32, value_ptr: *mut c_void) -> bool;
+
+    unsafe fn CGEventSourceKeyState(state_id: i32, key: u16) -> bool;
+}
+
+// QoS classes for thread scheduling
+// https://developer.apple.com/documentation/apple-silicon/tuning-your-code-s-performance-for-apple-silicon
+const QOS_CLASS_USER_INTERACTIVE: u32 = 0x21;
+const QOS_CLASS_DEFAULT: u32 = 0x15;
+const QOS_CLASS_UNSPECIFIED: u32 = 0x00;
+
+// declare external functions from libSystem pthread
+unsafe extern "C" {
+    unsafe fn pthread_self() -> *mut c_void;
+
+    unsafe fn pthread_get_qos_class_np(
+        thread: *mut c_void,
+        qos_class: *mut u32,
+        relative_priority: *mut i32,
+    ) -> i32;
+
+    unsafe fn pthread_set_qos_class_self_np(qos_class: u32, relative_priority: i32) -> i32;
 }
 
 // declare external functions from macOS AppKit framework
@@ -121,12 +179,12 @@ unsafe fn objc_call_i32(obj: *const c_void, sel: *const c_void) -> i32 {
     objc_call!(obj, sel, i32)
 }
 
-/// Check if two NSPoint values are equal with floating point tolerance.
+/// Check if two NSPoint values are equal within the given tolerance.
 #[inline]
-fn ns_point_equals(p1: NSPoint, p2: NSPoint) -> bool {
-    let x_equal = (p1.x - p2.x).abs() < f64::EPSILON;
-    let y_equal = (p1.y - p2.y).abs() < f64::EPSILON;
-    x_equal && y_equal
+fn ns_point_near(p1: NSPoint, p2: NSPoint, tolerance: f64) -> bool {
+    let x_near = (p1.x - p2.x).abs() <= tolerance;
+    let y_near = (p1.y - p2.y).abs() <= tolerance;
+    x_near && y_near
 }
 
 /// Get UI element attribute value.
@@ -173,7 +231,10 @@ fn get_focused_element() -> Result<CFType, AppError> {
     unsafe {
         // check accessibility permission
         if !AXIsProcessTrusted() {
-            return Err("Accessibility permission not granted".into());
+            return Err(AppError::with_kind(
+                ErrorKind::PermissionDenied,
+                "Accessibility permission not granted",
+            ));
         }
 
         // create system-wide AXUIElement
@@ -188,6 +249,20 @@ fn get_focused_element() -> Result<CFType, AppError> {
     }
 }
 
+/// Get currently focused UI element, retrying once after enabling AXAPI for special apps.
+fn get_focused_element_with_retry() -> Result<CFType, AppError> {
+    match get_focused_element() {
+        Ok(element) => Ok(element),
+        Err(_) => {
+            // try to enable AXAPI for special applications
+            // inspired by https://github.com/0xfullex/selection-hook
+            let _ = enable_axapi_for_special_apps(false);
+            // retry getting focused element
+            get_focused_element()
+        }
+    }
+}
+
 /// Get application element by PID.
 fn get_application_element(pid: i32) -> Result<CFType, AppError> {
     unsafe {
@@ -261,14 +336,15 @@ fn get_frontmost_app_pid() -> Option<i32> {
 
 /// Enable AXAPI for special applications (Chrome/Chromium and Electron apps).
 /// Uses NSWorkspace to get frontmost app PID, bypassing AXAPI limitations.
-/// Each PID is cached for a short duration to avoid redundant processing.
-fn enable_axapi_for_special_apps() -> Result<(), AppError> {
+/// Each PID is cached for a short duration to avoid redundant processing,
+/// unless `force` is true.
+fn enable_axapi_for_special_apps(force: bool) -> Result<(), AppError> {
     // get frontmost app PID via NSWorkspace
     let pid = get_frontmost_app_pid().ok_or("Failed to get frontmost app PID")?;
     let now = Instant::now();
 
     // check if this PID was recently processed
-    {
+    if !force {
         let mut processed = PROCESSED_PIDS.lock()?;
         if processed.is_none() {
             *processed = Some(HashMap::new());
@@ -277,7 +353,9 @@ fn enable_axapi_for_special_apps() -> Result<(), AppError> {
         if let Some(ref map) = *processed {
             if let Some(&last_time) = map.get(&pid) {
                 // check if the cache is still valid
-                if now.duration_since(last_time).as_secs() < PID_CACHE_EXPIRE_SECS {
+                if now.duration_since(last_time).as_secs()
+                    < PID_CACHE_EXPIRE_SECS.load(Ordering::Relaxed)
+                {
                     // recently processed, skip
                     return Ok(());
                 }
@@ -309,6 +387,27 @@ fn enable_axapi_for_special_apps() -> Result<(), AppError> {
     Ok(())
 }
 
+/// Set how long an enabled PID is cached before AXAPI is enabled again (0 disables caching).
+pub fn set_axapi_cache_duration(seconds: u64) {
+    PID_CACHE_EXPIRE_SECS.store(seconds, Ordering::Relaxed);
+
+    // drop cached PIDs so the new duration takes effect immediately
+    reset_axapi_cache();
+}
+
+/// Clear the per-process AXAPI enabling cache.
+pub fn reset_axapi_cache() {
+    PROCESSED_PIDS.clear_poison();
+    if let Ok(mut processed) = PROCESSED_PIDS.lock() {
+        *processed = None;
+    }
+}
+
+/// Enable AXAPI for the frontmost application on demand, ignoring the PID cache.
+pub fn enable_accessibility_for_frontmost() -> Result<(), AppError> {
+    enable_axapi_for_special_apps(true)
+}
+
 /// Get selected text attribute from given element.
 fn get_selected_text(element: &CFType) -> Option<String> {
     // try to get selected text
@@ -344,19 +443,25 @@ fn get_selected_range(element: &CFType) -> Result<CFRange, AppError> {
     }
 }
 
+/// Set selected text range of given element.
+fn set_selected_range(element: &CFType, range: CFRange) -> Result<(), AppError> {
+    unsafe {
+        // create AXValue object
+        let range_ptr = AXValueCreate(AX_VALUE_TYPE_CF_RANGE, &range as *const _ as _);
+        if range_ptr.is_null() {
+            return Err("Failed to create new range AXValue".into());
+        }
+        let range_value = CFType::wrap_under_create_rule(range_ptr);
+
+        // set new selection range
+        set_element_attribute(element, "AXSelectedTextRange", range_value.as_CFTypeRef())
+    }
+}
+
 /// Get selected text in currently focused element.
 pub fn get_selection() -> Result<String, AppError> {
-    // get focused element, if failed, try to enable AXAPI for special apps and retry
-    let focused_element = match get_focused_element() {
-        Ok(element) => element,
-        Err(_) => {
-            // try to enable AXAPI for special applications
-            // inspired by https://github.com/0xfullex/selection-hook
-            let _ = enable_axapi_for_special_apps();
-            // retry getting focused element
-            get_focused_element()?
-        }
-    };
+    // get focused element
+    let focused_element = get_focused_element_with_retry()?;
 
     // Strategy 1: try to get selected text directly from focused element
     if let Some(text) = get_selected_text(&focused_element) {
@@ -388,11 +493,73 @@ pub fn get_selection() -> Result<String, AppError> {
     Ok(String::new())
 }
 
+/// Dump a compact tree of the focused element and its descendants for diagnostics.
+/// Each line shows the accessibility role and the length of the selected text if any.
+/// At most `max_nodes` elements are visited.
+pub fn dump_focused_tree(max_nodes: usize) -> Result<String, AppError> {
+    let focused_element = get_focused_element_with_retry()?;
+
+    let mut output = String::new();
+    let mut remaining = max_nodes.max(1);
+    if !dump_element_tree(&focused_element, 0, &mut remaining, &mut output) {
+        output.push_str(&format!("... truncated at {} nodes\n", max_nodes.max(1)));
+    }
+
+    Ok(output)
+}
+
+/// Append an element and its children to the tree dump.
+/// Returns false if the node limit was reached before the whole tree was visited.
+fn dump_element_tree(
+    element: &CFType,
+    depth: usize,
+    remaining: &mut usize,
+    output: &mut String,
+) -> bool {
+    if *remaining == 0 {
+        return false;
+    }
+    *remaining -= 1;
+
+    // describe current element
+    let role = get_element_attribute(element, "AXRole")
+        .ok()
+        .and_then(|role| role.downcast::<CFString>())
+        .map(|role| role.to_string())
+        .unwrap_or_else(|| "?".to_string());
+    output.push_str(&"  ".repeat(depth));
+    output.push_str(&role);
+    if let Some(text) = get_selected_text(element).filter(|text| !text.is_empty()) {
+        output.push_str(&format!(" [selected: {} chars]", text.chars().count()));
+    }
+    output.push('\n');
+
+    // describe children elements
+    if let Ok(ax_children) = get_element_attribute(element, "AXChildren") {
+        if let Some(children) = ax_children.downcast::<CFArray>() {
+            for i in 0..children.len() {
+                unsafe {
+                    if let Some(child_ptr) = children.get(i).map(|item| *item as CFTypeRef) {
+                        if !child_ptr.is_null() {
+                            let child = CFType::wrap_under_get_rule(child_ptr);
+                            if !dump_element_tree(&child, depth + 1, remaining, output) {
+                                return false;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    true
+}
+
 /// Get the coordinates of the bottom-right corner of the selected text.
 pub fn get_cursor_location() -> Result<(i32, i32), AppError> {
     unsafe {
         // get focused element
-        let focused_element = get_focused_element()?;
+        let focused_element = get_focused_element_with_retry()?;
 
         // get selected text range
         let selected_range = get_selected_range(&focused_element)?;
@@ -465,10 +632,65 @@ pub fn get_cursor_location() -> Result<(i32, i32), AppError> {
     }
 }
 
+/// Get the whole text of currently focused element.
+pub fn get_focused_text() -> Result<String, AppError> {
+    // get focused element
+    let focused_element = get_focused_element_with_retry()?;
+
+    get_element_attribute(&focused_element, "AXValue")?
+        .downcast::<CFString>()
+        .map(|value| value.to_string())
+        .ok_or_else(|| "Focused element has no text value".into())
+}
+
+/// Get the number of characters in currently focused element.
+pub fn get_focused_text_length() -> Result<usize, AppError> {
+    // get focused element
+    let focused_element = get_focused_element_with_retry()?;
+
+    get_text_length(&focused_element)
+}
+
+/// Get the number of characters in given element.
+fn get_text_length(element: &CFType) -> Result<usize, AppError> {
+    // try character count attribute first
+    if let Ok(count) = get_element_attribute(element, "AXNumberOfCharacters") {
+        if let Some(count) = count.downcast::<CFNumber>().and_then(|n| n.to_i64()) {
+            return Ok(count.max(0) as usize);
+        }
+    }
+
+    // fall back to counting characters of element value
+    get_element_attribute(element, "AXValue")?
+        .downcast::<CFString>()
+        .map(|value| value.to_string().encode_utf16().count())
+        .ok_or_else(|| "Focused element has no text value".into())
+}
+
+/// Set window opacity through `NSWindow.alphaValue`.
+pub fn set_window_opacity(ns_window: *mut c_void, opacity: f64) -> Result<(), AppError> {
+    if ns_window.is_null() {
+        return Err("Invalid NSWindow pointer".into());
+    }
+
+    unsafe {
+        // call [window setAlphaValue:opacity]
+        type SetAlphaFn = unsafe extern "C" fn(*const c_void, *const c_void, f64);
+        let func: SetAlphaFn = std::mem::transmute(objc_msgSend as *const c_void);
+        func(
+            ns_window,
+            sel_registerName(c"setAlphaValue:".as_ptr()),
+            opacity,
+        );
+    }
+
+    Ok(())
+}
+
 /// Check if currently focused element is editable.
 pub fn is_cursor_editable() -> Result<bool, AppError> {
     // get focused element
-    let focused_element = get_focused_element()?;
+    let focused_element = get_focused_element_with_retry()?;
 
     // get role of focused element
     let ax_role = get_element_attribute(&focused_element, "AXRole")?;
@@ -512,43 +734,190 @@ pub fn is_ibeam_cursor() -> bool {
         let hot_spot = objc_call_point(current_cursor, hot_spot_sel);
 
         // check if hotSpot matches known I-Beam cursor hotSpots
-        ns_point_equals(hot_spot, NSPoint { x: 4.0, y: 9.0 })
-            || ns_point_equals(hot_spot, NSPoint { x: 16.0, y: 16.0 })
-            || ns_point_equals(hot_spot, NSPoint { x: 12.0, y: 11.0 })
+        let matches_default = DEFAULT_IBEAM_HOTSPOTS
+            .iter()
+            .any(|&(x, y)| ns_point_near(hot_spot, NSPoint { x, y }, IBEAM_HOTSPOT_TOLERANCE));
+        matches_default
+            || EXTRA_IBEAM_HOTSPOTS.lock().is_ok_and(|extra| {
+                extra.iter().any(|&(x, y)| {
+                    ns_point_near(hot_spot, NSPoint { x, y }, IBEAM_HOTSPOT_TOLERANCE)
+                })
+            })
+    }
+}
+
+/// Set additional I-Beam cursor hotSpots used by custom or Retina cursor variants.
+pub fn set_ibeam_hotspots(hotspots: Vec<(f64, f64)>) -> Result<(), AppError> {
+    *EXTRA_IBEAM_HOTSPOTS.lock()? = hotspots;
+    Ok(())
+}
+
+/// Check which modifier keys are physically held, in Meta, Control, Alt, Shift order.
+pub fn get_held_modifiers() -> [bool; 4] {
+    MODIFIER_KEY_CODES.map(|(left, right)| unsafe {
+        CGEventSourceKeyState(CG_EVENT_SOURCE_STATE_HID_SYSTEM_STATE, left)
+            || CGEventSourceKeyState(CG_EVENT_SOURCE_STATE_HID_SYSTEM_STATE, right)
+    })
+}
+
+// guard to restore the QoS class of the current thread on drop
+pub struct ThreadPriorityGuard {
+    qos_class: u32,
+    relative_priority: i32,
+}
+
+impl Drop for ThreadPriorityGuard {
+    fn drop(&mut self) {
+        unsafe {
+            pthread_set_qos_class_self_np(self.qos_class, self.relative_priority);
+        }
+    }
+}
+
+/// Raise the current thread to the user-interactive QoS class until the guard is dropped.
+/// Returns None if the QoS class could not be changed.
+pub fn boost_thread_priority() -> Option<ThreadPriorityGuard> {
+    unsafe {
+        let mut qos_class = QOS_CLASS_UNSPECIFIED;
+        let mut relative_priority = 0;
+        if pthread_get_qos_class_np(pthread_self(), &mut qos_class, &mut relative_priority) != 0 {
+            return None;
+        }
+
+        // unspecified class cannot be set back explicitly
+        if qos_class == QOS_CLASS_UNSPECIFIED {
+            qos_class = QOS_CLASS_DEFAULT;
+        }
+
+        if pthread_set_qos_class_self_np(QOS_CLASS_USER_INTERACTIVE, 0) != 0 {
+            return None;
+        }
+
+        Some(ThreadPriorityGuard {
+            qos_class,
+            relative_priority,
+        })
     }
 }
 
-/// Select specified number of characters from current cursor position backward.
-pub fn select_backward_chars(chars: usize) -> Result<(), AppError> {
+/// Remember the frontmost application and its focused element for `restore_focus`.
+pub fn remember_focus() -> Result<(), AppError> {
+    let pid = get_frontmost_app_pid().ok_or("Failed to get frontmost app PID")?;
+
+    // focused element is optional, activating the application alone often suffices
+    let element = get_focused_element().ok();
+
+    *REMEMBERED_FOCUS.lock()? = Some(RememberedFocus { pid, element });
+    Ok(())
+}
+
+/// Re-activate the remembered application and focus its remembered element (best effort).
+/// Returns false if nothing was remembered.
+pub fn restore_focus() -> Result<bool, AppError> {
+    let remembered = REMEMBERED_FOCUS.lock()?;
+    let Some(focus) = remembered.as_ref() else {
+        return Ok(false);
+    };
+
+    unsafe {
+        // get NSRunningApplication class
+        let running_app_class = objc_getClass(c"NSRunningApplication".as_ptr());
+        if running_app_class.is_null() {
+            return Err("Failed to get NSRunningApplication class".into());
+        }
+
+        // call [NSRunningApplication runningApplicationWithProcessIdentifier:pid]
+        type WithPidFn = unsafe extern "C" fn(*const c_void, *const c_void, i32) -> *const c_void;
+        let with_pid: WithPidFn = std::mem::transmute(objc_msgSend as *const c_void);
+        let with_pid_sel = sel_registerName(c"runningApplicationWithProcessIdentifier:".as_ptr());
+        let running_app = with_pid(running_app_class, with_pid_sel, focus.pid);
+        if running_app.is_null() {
+            return Err("Remembered application is no longer running".into());
+        }
+
+        // call [runningApp activateWithOptions:]
+        type ActivateFn = unsafe extern "C" fn(*const c_void, *const c_void, u64) -> bool;
+        let activate: ActivateFn = std::mem::transmute(objc_msgSend as *const c_void);
+        let activate_sel = sel_registerName(c"activateWithOptions:".as_ptr());
+        if !activate(
+            running_app,
+            activate_sel,
+            NS_APPLICATION_ACTIVATE_IGNORING_OTHER_APPS,
+        ) {
+            return Err("Failed to activate remembered application".into());
+        }
+    }
+
+    // focus remembered element
+    if let Some(element) = &focus.element {
+        let _ = set_element_attribute(element, "AXFocused", unsafe {
+            core_foundation::boolean::kCFBooleanTrue as CFTypeRef
+        });
+    }
+
+    Ok(true)
+}
+
+/// Replace selected text in currently focused element.
+pub fn replace_selection(text: &str) -> Result<(), AppError> {
+    // get focused element
+    let focused_element = get_focused_element_with_retry()?;
+
+    // set selected text attribute to replace selection
+    set_element_attribute(
+        &focused_element,
+        "AXSelectedText",
+        CFString::new(text).as_CFTypeRef(),
+    )
+}
+
+/// Select specified number of characters ending `skip` characters before the cursor.
+pub fn select_backward_range(skip: usize, chars: usize) -> Result<(), AppError> {
     unsafe {
         // get focused element
-        let focused_element = get_focused_element()?;
+        let focused_element = get_focused_element_with_retry()?;
 
         // get selected text range
         let orig_range = get_selected_range(&focused_element)?;
 
         // calculate new selection range
+        let end = orig_range.location + orig_range.length - skip as isize;
         let new_range = CFRange {
-            location: (orig_range.location + orig_range.length - chars as isize).max(0),
+            location: (end - chars as isize).max(0),
             length: chars as isize,
         };
 
-        // create AXValue object
-        let new_range_ptr = AXValueCreate(AX_VALUE_TYPE_CF_RANGE, &new_range as *const _ as _);
-        if new_range_ptr.is_null() {
-            return Err("Failed to create new range AXValue".into());
-        }
-        let new_range_value = CFType::wrap_under_create_rule(new_range_ptr);
+        set_selected_range(&focused_element, new_range)
+    }
+}
 
-        // set new selection range
-        set_element_attribute(
-            &focused_element,
-            "AXSelectedTextRange",
-            new_range_value.as_CFTypeRef(),
-        )?;
+/// Get the selection range in currently focused element as (location, length).
+pub fn get_selection_range() -> Result<(usize, usize), AppError> {
+    let focused_element = get_focused_element_with_retry()?;
+    let range = get_selected_range(&focused_element)?;
+    Ok((range.location.max(0) as usize, range.length.max(0) as usize))
+}
 
-        Ok(())
+/// Select the given range in currently focused element.
+pub fn set_selection_range(location: usize, length: usize) -> Result<(), AppError> {
+    let focused_element = get_focused_element_with_retry()?;
+
+    // validate range against text length to avoid AX errors
+    let text_length = get_text_length(&focused_element)?;
+    if location
+        .checked_add(length)
+        .is_none_or(|end| end > text_length)
+    {
+        return Err("Selection range is out of bounds".into());
     }
+
+    set_selected_range(
+        &focused_element,
+        CFRange {
+            location: location as isize,
+            length: length as isize,
+        },
+    )
 }
 
 /// Get application identifier from an application path.
@@ -579,6 +948,26 @@ pub fn get_app_id(app_path: &Path) -> Result<String, AppError> {
 
 /// Get the Bundle ID of the frontmost application.
 pub fn get_frontmost_app_id() -> Option<String> {
+    let pid = get_frontmost_app_pid()?;
+
+    // reuse cached app id while the same application stays frontmost
+    if let Ok(cache) = APP_ID_CACHE.lock() {
+        if let Some((cached_pid, cached_at, app_id)) = cache.as_ref() {
+            if *cached_pid == pid && cached_at.elapsed() < APP_ID_CACHE_TTL {
+                return app_id.clone();
+            }
+        }
+    }
+
+    let app_id = query_frontmost_bundle_id();
+    if let Ok(mut cache) = APP_ID_CACHE.lock() {
+        *cache = Some((pid, Instant::now(), app_id.clone()));
+    }
+    app_id
+}
+
+/// Get the bundle identifier of the frontmost application.
+fn query_frontmost_bundle_id() -> Option<String> {
     unsafe {
         let frontmost_app = get_frontmost_application()?;
 
@@ -599,6 +988,18 @@ pub fn get_frontmost_app_id() -> Option<String> {
     }
 }
 
+/// Get the title of the focused window of the frontmost application.
+pub fn get_frontmost_window_title() -> Option<String> {
+    let pid = get_frontmost_app_pid()?;
+    let app_element = get_application_element(pid).ok()?;
+    let window = get_element_attribute(&app_element, "AXFocusedWindow").ok()?;
+    let title = get_element_attribute(&window, "AXTitle").ok()?;
+    title
+        .downcast::<CFString>()
+        .map(|t| t.to_string())
+        .filter(|t| !t.is_empty())
+}
+
 /// Get the current website URL from the frontmost browser.
 pub fn get_frontmost_url() -> Option<String> {
     unsafe {
@@ -670,3 +1071,81 @@ fn find_url_in_element(element: &CFType, depth: usize, max_depth: usize) -> Opti
         None
     }
 }
+
+// IOKit power management message types
+const K_IO_MESSAGE_CAN_SYSTEM_SLEEP: u32 = 0xE000_0270;
+const K_IO_MESSAGE_SYSTEM_WILL_SLEEP: u32 = 0xE000_0280;
+const K_IO_MESSAGE_SYSTEM_HAS_POWERED_ON: u32 = 0xE000_0300;
+
+// IOKit power management callback type
+type IOServiceInterestCallback =
+    extern "C" fn(refcon: *mut c_void, service: u32, message_type: u32, argument: *mut c_void);
+
+// declare external functions from macOS IOKit framework
+#[link(name = "IOKit", kind = "framework")]
+unsafe extern "C" {
+    unsafe fn IORegisterForSystemPower(
+        refcon: *mut c_void,
+        the_port_ref: *mut *mut c_void,
+        callback: IOServiceInterestCallback,
+        notifier: *mut u32,
+    ) -> u32;
+    unsafe fn IONotificationPortGetRunLoopSource(notify: *mut c_void) -> CFRunLoopSourceRef;
+    unsafe fn IOAllowPowerChange(kernel_port: u32, notification_id: isize) -> i32;
+}
+
+// root power domain port and callback invoked on system wake
+static ROOT_POWER_PORT: AtomicU32 = AtomicU32::new(0);
+static WAKE_CALLBACK: OnceLock<fn()> = OnceLock::new();
+
+/// Handle IOKit system power messages.
+extern "C" fn system_power_callback(
+    _refcon: *mut c_void,
+    _service: u32,
+    message_type: u32,
+    argument: *mut c_void,
+) {
+    match message_type {
+        // sleep must be acknowledged, otherwise the system waits for a timeout
+        K_IO_MESSAGE_CAN_SYSTEM_SLEEP | K_IO_MESSAGE_SYSTEM_WILL_SLEEP => unsafe {
+            IOAllowPowerChange(ROOT_POWER_PORT.load(Ordering::Relaxed), argument as isize);
+        },
+        K_IO_MESSAGE_SYSTEM_HAS_POWERED_ON => {
+            if let Some(callback) = WAKE_CALLBACK.get() {
+                callback();
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Register a callback invoked when the system wakes from sleep.
+pub fn register_wake_listener(callback: fn()) -> Result<(), AppError> {
+    WAKE_CALLBACK
+        .set(callback)
+        .map_err(|_| "Wake listener is already registered")?;
+
+    // power notifications are delivered through a dedicated run loop
+    std::thread::spawn(|| unsafe {
+        let mut notify_port: *mut c_void = std::ptr::null_mut();
+        let mut notifier: u32 = 0;
+        let root_port = IORegisterForSystemPower(
+            std::ptr::null_mut(),
+            &mut notify_port,
+            system_power_callback,
+            &mut notifier,
+        );
+        if root_port == 0 {
+            log::error!("Failed to register for system power notifications");
+            return;
+        }
+        ROOT_POWER_PORT.store(root_port, Ordering::Relaxed);
+
+        let source =
+            CFRunLoopSource::wrap_under_get_rule(IONotificationPortGetRunLoopSource(notify_port));
+        CFRunLoop::get_current().add_source(&source, kCFRunLoopDefaultMode);
+        CFRunLoop::run_current();
+    });
+
+    Ok(())
+}
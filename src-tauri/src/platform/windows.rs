@@ -1,24 +1,41 @@
 use crate::error::AppError;
+use log::warn;
 use std::fs;
 use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
 use windows::core::{Interface, PWSTR};
-use windows::Win32::Foundation::MAX_PATH;
+use windows::Win32::Foundation::{COLORREF, ERROR_SUCCESS, HANDLE, HWND, MAX_PATH};
 use windows::Win32::System::Com::{
     CoCreateInstance, CoInitializeEx, CoUninitialize, CLSCTX_ALL, COINIT_APARTMENTTHREADED,
 };
+use windows::Win32::System::Power::{
+    PowerRegisterSuspendResumeNotification, DEVICE_NOTIFY_SUBSCRIBE_PARAMETERS, HPOWERNOTIFY,
+};
 use windows::Win32::System::Threading::{
-    OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION,
+    GetCurrentThread, GetThreadPriority, OpenProcess, QueryFullProcessImageNameW,
+    SetThreadPriority, PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION, THREAD_PRIORITY,
+    THREAD_PRIORITY_HIGHEST,
 };
 use windows::Win32::UI::Accessibility::{
     CUIAutomation, IUIAutomation, IUIAutomationElement, IUIAutomationLegacyIAccessiblePattern,
-    IUIAutomationTextPattern, IUIAutomationTextRange, IUIAutomationValuePattern,
-    TextPatternRangeEndpoint_Start, TextUnit_Character, TreeScope_Descendants,
-    UIA_ControlTypePropertyId, UIA_DocumentControlTypeId, UIA_EditControlTypeId,
-    UIA_LegacyIAccessiblePatternId, UIA_TextPatternId, UIA_ValuePatternId,
+    IUIAutomationTextPattern, IUIAutomationTextRange, IUIAutomationTreeWalker,
+    IUIAutomationValuePattern, TextPatternRangeEndpoint_End, TextPatternRangeEndpoint_Start,
+    TextUnit_Character, TreeScope_Descendants, UIA_ControlTypePropertyId,
+    UIA_DocumentControlTypeId, UIA_EditControlTypeId, UIA_LegacyIAccessiblePatternId,
+    UIA_TextPatternId, UIA_ValuePatternId,
+};
+use windows::Win32::UI::Input::KeyboardAndMouse::{
+    GetAsyncKeyState, VIRTUAL_KEY, VK_LCONTROL, VK_LMENU, VK_LSHIFT, VK_LWIN, VK_RCONTROL,
+    VK_RMENU, VK_RSHIFT, VK_RWIN,
 };
 use windows::Win32::UI::WindowsAndMessaging::{
-    GetCursorInfo, GetForegroundWindow, GetWindowThreadProcessId, LoadCursorW, CURSORINFO,
-    CURSOR_SHOWING, IDC_IBEAM,
+    GetCursorInfo, GetForegroundWindow, GetWindowLongPtrW, GetWindowTextLengthW, GetWindowTextW,
+    GetWindowThreadProcessId, LoadCursorW, SetForegroundWindow, SetLayeredWindowAttributes,
+    SetWindowLongPtrW, SetWindowPos, ShowWindow, CURSORINFO, CURSOR_SHOWING,
+    DEVICE_NOTIFY_CALLBACK, GWL_EXSTYLE, HWND_NOTOPMOST, HWND_TOPMOST, IDC_IBEAM, LWA_ALPHA,
+    PBT_APMRESUMEAUTOMATIC, SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE, SW_SHOWNOACTIVATE,
+    WS_EX_LAYERED, WS_EX_NOACTIVATE,
 };
 
 // bounds validation constants
@@ -26,6 +43,32 @@ const MIN_VALID_WIDTH: f64 = 1.0;
 const MAX_VALID_HEIGHT: f64 = 100.0;
 const MAX_VALID_COORDINATE: f64 = 10000.0;
 
+// left/right modifier virtual keys in Meta, Control, Alt, Shift order
+const MODIFIER_KEYS: [(VIRTUAL_KEY, VIRTUAL_KEY); 4] = [
+    (VK_LWIN, VK_RWIN),
+    (VK_LCONTROL, VK_RCONTROL),
+    (VK_LMENU, VK_RMENU),
+    (VK_LSHIFT, VK_RSHIFT),
+];
+
+// cached frontmost URL keyed by foreground window handle, with lookup time
+static URL_CACHE: Mutex<Option<(isize, Instant, Option<String>)>> = Mutex::new(None);
+const URL_CACHE_TTL: Duration = Duration::from_secs(2);
+
+// cached frontmost app id keyed by foreground window handle, with lookup time
+static APP_ID_CACHE: Mutex<Option<(isize, Instant, Option<String>)>> = Mutex::new(None);
+const APP_ID_CACHE_TTL: Duration = Duration::from_millis(500);
+
+// foreground window remembered by `remember_focus`
+static REMEMBERED_FOCUS: Mutex<Option<isize>> = Mutex::new(None);
+
+// COM initialization attempts and initial retry delay, doubled after each failure
+const COM_INIT_ATTEMPTS: u32 = 3;
+const COM_INIT_RETRY_DELAY: Duration = Duration::from_millis(10);
+
+// returned by GetThreadPriority on failure
+const THREAD_PRIORITY_ERROR_RETURN: i32 = 0x7FFF_FFFF;
+
 // editable legacy control roles
 const ROLE_SYSTEM_TEXT: u32 = 42;
 const ROLE_SYSTEM_COMBOBOX: u32 = 46;
@@ -60,20 +103,31 @@ struct ComGuard {
 
 impl ComGuard {
     /// Initialize COM environment.
+    /// Transient failures are retried with a short backoff before giving up.
     fn new() -> Result<Self, AppError> {
-        unsafe {
-            let result = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+        let mut delay = COM_INIT_RETRY_DELAY;
+        for attempt in 1..=COM_INIT_ATTEMPTS {
+            let result = unsafe { CoInitializeEx(None, COINIT_APARTMENTTHREADED) };
             if result.is_ok() {
                 // successfully initialized, need to release on drop
-                Ok(ComGuard { initialized: true })
+                return Ok(ComGuard { initialized: true });
             } else if result == windows::Win32::Foundation::RPC_E_CHANGED_MODE {
                 // COM already initialized by other code, no need to release
-                Ok(ComGuard { initialized: false })
-            } else {
-                // other errors
-                Err("Failed to initialize COM".into())
+                return Ok(ComGuard { initialized: false });
+            }
+
+            // other errors, retry after a short delay
+            warn!(
+                "Failed to initialize COM (attempt {}/{}): HRESULT {:#010X}",
+                attempt, COM_INIT_ATTEMPTS, result.0 as u32
+            );
+            if attempt < COM_INIT_ATTEMPTS {
+                std::thread::sleep(delay);
+                delay *= 2;
             }
         }
+
+        Err("Failed to initialize COM".into())
     }
 }
 
@@ -127,6 +181,17 @@ fn get_selected_range(element: &IUIAutomationElement) -> Result<IUIAutomationTex
     }
 }
 
+/// Get whole document text range from given element.
+fn get_document_range(element: &IUIAutomationElement) -> Result<IUIAutomationTextRange, AppError> {
+    unsafe {
+        element
+            .GetCurrentPattern(UIA_TextPatternId)
+            .and_then(|p| p.cast::<IUIAutomationTextPattern>())
+            .and_then(|tp| tp.DocumentRange())
+            .map_err(|_| "Failed to get document range".into())
+    }
+}
+
 /// Get selected text in currently focused element.
 pub fn get_selection() -> Result<String, AppError> {
     unsafe {
@@ -148,6 +213,87 @@ pub fn get_selection() -> Result<String, AppError> {
     }
 }
 
+/// Dump a compact tree of the focused element and its descendants for diagnostics.
+/// Each line shows the control type, class name and the length of the selected text if any.
+/// At most `max_nodes` elements are visited.
+pub fn dump_focused_tree(max_nodes: usize) -> Result<String, AppError> {
+    unsafe {
+        // initialize COM
+        let _com = ComGuard::new()?;
+
+        // create UI Automation instance
+        let automation: IUIAutomation = CoCreateInstance(&CUIAutomation, None, CLSCTX_ALL)
+            .map_err(|e| format!("Failed to create UI Automation instance: {}", e))?;
+
+        // get focused element and a walker over the control view
+        let focused_element = automation
+            .GetFocusedElement()
+            .map_err(|e| format!("Failed to get focused element: {}", e))?;
+        let walker = automation
+            .ControlViewWalker()
+            .map_err(|e| format!("Failed to create tree walker: {}", e))?;
+
+        let mut output = String::new();
+        let mut remaining = max_nodes.max(1);
+        if !dump_element_tree(&focused_element, &walker, 0, &mut remaining, &mut output) {
+            output.push_str(&format!("... truncated at {} nodes\n", max_nodes.max(1)));
+        }
+
+        Ok(output)
+    }
+}
+
+/// Append an element and its descendants to the tree dump.
+/// Returns false if the node limit was reached before the whole tree was visited.
+unsafe fn dump_element_tree(
+    element: &IUIAutomationElement,
+    walker: &IUIAutomationTreeWalker,
+    depth: usize,
+    remaining: &mut usize,
+    output: &mut String,
+) -> bool {
+    if *remaining == 0 {
+        return false;
+    }
+    *remaining -= 1;
+
+    // describe current element
+    let control_type = element.CurrentControlType().map(|id| id.0).unwrap_or(0);
+    let localized_type = element
+        .CurrentLocalizedControlType()
+        .map(|name| name.to_string())
+        .unwrap_or_default();
+    let class_name = element
+        .CurrentClassName()
+        .map(|name| name.to_string())
+        .unwrap_or_default();
+    output.push_str(&"  ".repeat(depth));
+    output.push_str(&format!("{} ({})", localized_type, control_type));
+    if !class_name.is_empty() {
+        output.push_str(&format!(" class={}", class_name));
+    }
+    if let Some(text) = get_selected_range(element)
+        .ok()
+        .and_then(|range| range.GetText(-1).ok())
+        .map(|text| text.to_string())
+        .filter(|text| !text.is_empty())
+    {
+        output.push_str(&format!(" [selected: {} chars]", text.chars().count()));
+    }
+    output.push('\n');
+
+    // describe children elements
+    let mut child = walker.GetFirstChildElement(element).ok();
+    while let Some(current) = child {
+        if !dump_element_tree(&current, walker, depth + 1, remaining, output) {
+            return false;
+        }
+        child = walker.GetNextSiblingElement(&current).ok();
+    }
+
+    true
+}
+
 /// Get the coordinates of the bottom-right corner of the selected text.
 pub fn get_cursor_location() -> Result<(i32, i32), AppError> {
     unsafe {
@@ -225,6 +371,51 @@ pub fn get_cursor_location() -> Result<(i32, i32), AppError> {
     }
 }
 
+/// Get the whole text of currently focused element.
+pub fn get_focused_text() -> Result<String, AppError> {
+    unsafe {
+        // initialize COM
+        let _com = ComGuard::new()?;
+
+        // get focused element
+        let focused_element = get_focused_element()?;
+
+        get_document_range(&focused_element)?
+            .GetText(-1)
+            .map(|text| text.to_string())
+            .map_err(|_| "Failed to get text".into())
+    }
+}
+
+/// Get the number of characters in currently focused element.
+pub fn get_focused_text_length() -> Result<usize, AppError> {
+    unsafe {
+        // initialize COM
+        let _com = ComGuard::new()?;
+
+        // get focused element
+        let focused_element = get_focused_element()?;
+
+        // try reading whole document text through text pattern first
+        if let Ok(text) = focused_element
+            .GetCurrentPattern(UIA_TextPatternId)
+            .and_then(|p| p.cast::<IUIAutomationTextPattern>())
+            .and_then(|tp| tp.DocumentRange())
+            .and_then(|range| range.GetText(-1))
+        {
+            return Ok(text.len());
+        }
+
+        // fall back to value pattern
+        focused_element
+            .GetCurrentPattern(UIA_ValuePatternId)
+            .and_then(|p| p.cast::<IUIAutomationValuePattern>())
+            .and_then(|vp| vp.CurrentValue())
+            .map(|value| value.len())
+            .map_err(|_| "Focused element has no text value".into())
+    }
+}
+
 /// Check if currently focused element is editable.
 pub fn is_cursor_editable() -> Result<bool, AppError> {
     unsafe {
@@ -299,8 +490,77 @@ pub fn is_ibeam_cursor() -> bool {
     }
 }
 
-/// Select specified number of characters from current cursor position backward.
-pub fn select_backward_chars(chars: usize) -> Result<(), AppError> {
+/// Check which modifier keys are physically held, in Meta, Control, Alt, Shift order.
+pub fn get_held_modifiers() -> [bool; 4] {
+    let is_held = |key: VIRTUAL_KEY| unsafe { GetAsyncKeyState(key.0 as i32) } as u16 & 0x8000 != 0;
+    MODIFIER_KEYS.map(|(left, right)| is_held(left) || is_held(right))
+}
+
+// guard to restore the priority of the current thread on drop
+pub struct ThreadPriorityGuard {
+    priority: i32,
+}
+
+impl Drop for ThreadPriorityGuard {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = SetThreadPriority(GetCurrentThread(), THREAD_PRIORITY(self.priority));
+        }
+    }
+}
+
+/// Raise the current thread to the highest priority until the guard is dropped.
+/// Returns None if the priority could not be changed.
+pub fn boost_thread_priority() -> Option<ThreadPriorityGuard> {
+    unsafe {
+        let thread = GetCurrentThread();
+        let priority = GetThreadPriority(thread);
+        if priority == THREAD_PRIORITY_ERROR_RETURN {
+            return None;
+        }
+
+        SetThreadPriority(thread, THREAD_PRIORITY_HIGHEST).ok()?;
+        Some(ThreadPriorityGuard { priority })
+    }
+}
+
+/// Remember the foreground window for `restore_focus`.
+pub fn remember_focus() -> Result<(), AppError> {
+    let hwnd = unsafe { GetForegroundWindow() };
+    if hwnd.is_invalid() {
+        return Err("Failed to get foreground window".into());
+    }
+
+    *REMEMBERED_FOCUS.lock()? = Some(hwnd.0 as isize);
+    Ok(())
+}
+
+/// Bring the remembered window back to the foreground, which restores its focused control.
+/// Returns false if nothing was remembered.
+pub fn restore_focus() -> Result<bool, AppError> {
+    let Some(hwnd) = *REMEMBERED_FOCUS.lock()? else {
+        return Ok(false);
+    };
+
+    unsafe {
+        if !SetForegroundWindow(HWND(hwnd as _)).as_bool() {
+            return Err("Failed to restore foreground window".into());
+        }
+    }
+
+    Ok(true)
+}
+
+/// Replace selected text in currently focused element.
+/// UI Automation has no API to edit a text range, and `ValuePattern` can only replace the
+/// whole value, losing formatting and undo history. So this is not supported on Windows,
+/// and callers always fall back to pasting.
+pub fn replace_selection(_text: &str) -> Result<(), AppError> {
+    Err("Replacing selection natively is not supported".into())
+}
+
+/// Select specified number of characters ending `skip` characters before the cursor.
+pub fn select_backward_range(skip: usize, chars: usize) -> Result<(), AppError> {
     unsafe {
         // initialize COM
         let _com = ComGuard::new()?;
@@ -311,15 +571,26 @@ pub fn select_backward_chars(chars: usize) -> Result<(), AppError> {
         // get first selected text range
         let text_range = get_selected_range(&focused_element)?;
 
-        // move endpoint backward
+        // move start endpoint backward
         text_range
             .MoveEndpointByUnit(
                 TextPatternRangeEndpoint_Start,
                 TextUnit_Character,
-                -(chars as i32),
+                -((skip + chars) as i32),
             )
             .map_err(|_| "Failed to move endpoint backward")?;
 
+        // move end endpoint backward past skipped characters
+        if skip > 0 {
+            text_range
+                .MoveEndpointByUnit(
+                    TextPatternRangeEndpoint_End,
+                    TextUnit_Character,
+                    -(skip as i32),
+                )
+                .map_err(|_| "Failed to move end endpoint backward")?;
+        }
+
         // select new range
         text_range
             .Select()
@@ -329,6 +600,81 @@ pub fn select_backward_chars(chars: usize) -> Result<(), AppError> {
     }
 }
 
+/// Get the selection range in currently focused element as (location, length).
+pub fn get_selection_range() -> Result<(usize, usize), AppError> {
+    unsafe {
+        // initialize COM
+        let _com = ComGuard::new()?;
+
+        // get focused element and its first selected text range
+        let focused_element = get_focused_element()?;
+        let selection = get_selected_range(&focused_element)?;
+
+        // measure text from document start up to selection start
+        let before = get_document_range(&focused_element)?;
+        before
+            .MoveEndpointByRange(
+                TextPatternRangeEndpoint_End,
+                &selection,
+                TextPatternRangeEndpoint_Start,
+            )
+            .map_err(|_| "Failed to move end endpoint to selection")?;
+
+        let location = before.GetText(-1).map_err(|_| "Failed to get text")?.len();
+        let length = selection
+            .GetText(-1)
+            .map_err(|_| "Failed to get text")?
+            .len();
+        Ok((location, length))
+    }
+}
+
+/// Select the given range in currently focused element.
+pub fn set_selection_range(location: usize, length: usize) -> Result<(), AppError> {
+    unsafe {
+        // initialize COM
+        let _com = ComGuard::new()?;
+
+        let focused_element = get_focused_element()?;
+        let range = get_document_range(&focused_element)?;
+
+        // validate range against document length
+        let text_length = range.GetText(-1).map_err(|_| "Failed to get text")?.len();
+        if location
+            .checked_add(length)
+            .is_none_or(|end| end > text_length)
+        {
+            return Err("Selection range is out of bounds".into());
+        }
+
+        // collapse document range to its start
+        range
+            .MoveEndpointByRange(
+                TextPatternRangeEndpoint_End,
+                &range,
+                TextPatternRangeEndpoint_Start,
+            )
+            .map_err(|_| "Failed to collapse range")?;
+
+        // move range to location and extend it by length
+        range
+            .Move(TextUnit_Character, location as i32)
+            .map_err(|_| "Failed to move range")?;
+        range
+            .MoveEndpointByUnit(
+                TextPatternRangeEndpoint_End,
+                TextUnit_Character,
+                length as i32,
+            )
+            .map_err(|_| "Failed to extend range")?;
+
+        // select new range
+        range.Select().map_err(|_| "Failed to select new range")?;
+
+        Ok(())
+    }
+}
+
 /// Get application identifier from an application path.
 pub fn get_app_id(app_path: &Path) -> Result<String, AppError> {
     // canonicalize the application path
@@ -344,13 +690,31 @@ pub fn get_app_id(app_path: &Path) -> Result<String, AppError> {
 
 /// Get the executable path of the frontmost application.
 pub fn get_frontmost_app_id() -> Option<String> {
-    unsafe {
-        // get foreground window
-        let hwnd = GetForegroundWindow();
-        if hwnd.is_invalid() {
-            return None;
+    let hwnd = unsafe { GetForegroundWindow() };
+    if hwnd.is_invalid() {
+        return None;
+    }
+
+    // reuse cached app id while the same window stays in the foreground
+    let key = hwnd.0 as isize;
+    if let Ok(cache) = APP_ID_CACHE.lock() {
+        if let Some((cached_key, cached_at, app_id)) = cache.as_ref() {
+            if *cached_key == key && cached_at.elapsed() < APP_ID_CACHE_TTL {
+                return app_id.clone();
+            }
         }
+    }
+
+    let app_id = query_app_path(hwnd);
+    if let Ok(mut cache) = APP_ID_CACHE.lock() {
+        *cache = Some((key, Instant::now(), app_id.clone()));
+    }
+    app_id
+}
 
+/// Get the executable path of the process owning the given window.
+fn query_app_path(hwnd: HWND) -> Option<String> {
+    unsafe {
         // get process ID
         let mut pid: u32 = 0;
         GetWindowThreadProcessId(hwnd, Some(&mut pid));
@@ -379,18 +743,62 @@ pub fn get_frontmost_app_id() -> Option<String> {
     }
 }
 
-/// Get the current website URL from the frontmost browser.
-pub fn get_frontmost_url() -> Option<String> {
+/// Get the title of the foreground window.
+pub fn get_frontmost_window_title() -> Option<String> {
     unsafe {
-        // initialize COM
-        let _com = ComGuard::new().ok()?;
-
-        // get foreground window
         let hwnd = GetForegroundWindow();
         if hwnd.is_invalid() {
             return None;
         }
 
+        let length = GetWindowTextLengthW(hwnd);
+        if length <= 0 {
+            return None;
+        }
+
+        let mut buffer = vec![0u16; length as usize + 1];
+        let copied = GetWindowTextW(hwnd, &mut buffer);
+        if copied <= 0 {
+            return None;
+        }
+
+        String::from_utf16(&buffer[..copied as usize]).ok()
+    }
+}
+
+/// Get the current website URL from the frontmost browser.
+/// Results are cached per foreground window for a short time, since walking the
+/// UI Automation tree is expensive and blacklist checks run frequently.
+pub fn get_frontmost_url() -> Option<String> {
+    // get foreground window
+    let hwnd = unsafe { GetForegroundWindow() };
+    if hwnd.is_invalid() {
+        return None;
+    }
+
+    // reuse cached URL while the same window stays in the foreground
+    let key = hwnd.0 as isize;
+    if let Ok(cache) = URL_CACHE.lock() {
+        if let Some((cached_key, cached_at, url)) = cache.as_ref() {
+            if *cached_key == key && cached_at.elapsed() < URL_CACHE_TTL {
+                return url.clone();
+            }
+        }
+    }
+
+    let url = find_frontmost_url(hwnd);
+    if let Ok(mut cache) = URL_CACHE.lock() {
+        *cache = Some((key, Instant::now(), url.clone()));
+    }
+    url
+}
+
+/// Find the website URL in the given browser window through UI Automation.
+fn find_frontmost_url(hwnd: HWND) -> Option<String> {
+    unsafe {
+        // initialize COM
+        let _com = ComGuard::new().ok()?;
+
         // create UI Automation instance
         let automation: IUIAutomation = CoCreateInstance(&CUIAutomation, None, CLSCTX_ALL).ok()?;
 
@@ -444,3 +852,110 @@ unsafe fn find_url_in_element(
         None
     }
 }
+
+/// Set window opacity using a layered window.
+pub fn set_window_opacity(hwnd: *mut std::ffi::c_void, opacity: f64) -> Result<(), AppError> {
+    unsafe {
+        let hwnd = HWND(hwnd);
+
+        // layered style is required for per-window alpha
+        let ex_style = GetWindowLongPtrW(hwnd, GWL_EXSTYLE);
+        SetWindowLongPtrW(hwnd, GWL_EXSTYLE, ex_style | WS_EX_LAYERED.0 as isize);
+
+        SetLayeredWindowAttributes(
+            hwnd,
+            COLORREF(0),
+            (opacity * 255.0).round() as u8,
+            LWA_ALPHA,
+        )
+        .map_err(|e| format!("Failed to set window opacity: {}", e).into())
+    }
+}
+
+/// Set or clear the topmost state of a window so it floats above fullscreen windows.
+pub fn set_window_topmost(hwnd: *mut std::ffi::c_void, topmost: bool) -> Result<(), AppError> {
+    unsafe {
+        let hwnd = HWND(hwnd);
+
+        // move window into or out of the topmost z-order band without activating it,
+        // the WS_EX_TOPMOST style follows the z-order band and cannot be set directly
+        let insert_after = if topmost {
+            HWND_TOPMOST
+        } else {
+            HWND_NOTOPMOST
+        };
+        SetWindowPos(
+            hwnd,
+            Some(insert_after),
+            0,
+            0,
+            0,
+            0,
+            SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE,
+        )
+        .map_err(|e| format!("Failed to set window topmost: {}", e))?;
+
+        Ok(())
+    }
+}
+
+/// Apply non-activating extended style so clicking the window doesn't take focus.
+pub fn set_window_noactivate(hwnd: *mut std::ffi::c_void) {
+    unsafe {
+        let hwnd = HWND(hwnd);
+        let ex_style = GetWindowLongPtrW(hwnd, GWL_EXSTYLE);
+        SetWindowLongPtrW(hwnd, GWL_EXSTYLE, ex_style | WS_EX_NOACTIVATE.0 as isize);
+    }
+}
+
+/// Show a window without activating it, keeping focus in the underlying application.
+pub fn show_window_noactivate(hwnd: *mut std::ffi::c_void) {
+    unsafe {
+        let _ = ShowWindow(HWND(hwnd), SW_SHOWNOACTIVATE);
+    }
+}
+
+// callback invoked on system wake
+static WAKE_CALLBACK: OnceLock<fn()> = OnceLock::new();
+
+/// Handle suspend/resume power notifications.
+unsafe extern "system" fn power_notify_callback(
+    _context: *const std::ffi::c_void,
+    event: u32,
+    _setting: *const std::ffi::c_void,
+) -> u32 {
+    if event == PBT_APMRESUMEAUTOMATIC {
+        if let Some(callback) = WAKE_CALLBACK.get() {
+            callback();
+        }
+    }
+    0
+}
+
+/// Register a callback invoked when the system wakes from sleep.
+pub fn register_wake_listener(callback: fn()) -> Result<(), AppError> {
+    WAKE_CALLBACK
+        .set(callback)
+        .map_err(|_| "Wake listener is already registered")?;
+
+    // subscription parameters must outlive the registration
+    let params: &'static mut DEVICE_NOTIFY_SUBSCRIBE_PARAMETERS =
+        Box::leak(Box::new(DEVICE_NOTIFY_SUBSCRIBE_PARAMETERS {
+            Callback: Some(power_notify_callback),
+            Context: std::ptr::null_mut(),
+        }));
+
+    let mut registration = HPOWERNOTIFY::default();
+    let result = unsafe {
+        PowerRegisterSuspendResumeNotification(
+            DEVICE_NOTIFY_CALLBACK,
+            HANDLE(params as *mut _ as *mut std::ffi::c_void),
+            &mut registration,
+        )
+    };
+    if result != ERROR_SUCCESS {
+        return Err(format!("Failed to register for power notifications: {:?}", result).into());
+    }
+
+    Ok(())
+}
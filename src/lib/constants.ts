@@ -77,6 +77,16 @@ export const TOOLBAR_OPACITY = {
   step: 5
 };
 
+/**
+ * Popup window opacity.
+ */
+export const POPUP_OPACITY = {
+  min: 0.1,
+  default: 1,
+  max: 1,
+  step: 0.05
+};
+
 /**
  * Popup corner radius in pixels.
  */
@@ -1,6 +1,6 @@
 import { PROMPT_MARK, SCRIPT_MARK, SEARCHER_MARK } from '$lib/constants';
 import { evalAsync, evalSync } from '$lib/evaluator';
-import { isMouseShortcut } from '$lib/helpers';
+import { errorKind, errorMessage, isMouseShortcut } from '$lib/helpers';
 import { m } from '$lib/paraglide/messages';
 import { denoPath, entries, historySize, nodePath, prompts, pythonPath, scripts, searchers } from '$lib/stores.svelte';
 import type { Entry, Processor, Prompt, Rule, Script, WindowPlacement } from '$lib/types';
@@ -110,7 +110,7 @@ export const GENERAL_ACTIONS: Processor[] = [
       // open each URL
       urls.forEach((url) => {
         openUrl(url).catch((error) => {
-          console.error(`Failed to open URL ${url}: ${error}`);
+          console.error(`Failed to open URL ${url}: ${errorMessage(error)}`);
         });
       });
       return '';
@@ -128,7 +128,7 @@ export const GENERAL_ACTIONS: Processor[] = [
       // open each file path
       paths.forEach((path) => {
         openPath(path).catch((error) => {
-          console.error(`Failed to open path ${path}: ${error}`);
+          console.error(`Failed to open path ${path}: ${errorMessage(error)}`);
         });
       });
       return '';
@@ -462,7 +462,7 @@ async function executeScript(script: Script, entry: Entry): Promise<Result> {
             return { text: evalSync(data, code) };
           }
         } catch (error) {
-          console.error(`Failed to execute JavaScript in WebView: ${error}`);
+          console.error(`Failed to execute JavaScript in WebView: ${errorMessage(error)}`);
         }
       }
 
@@ -493,7 +493,11 @@ async function executeScript(script: Script, entry: Entry): Promise<Result> {
       throw new Error(`unsupported script language: ${language}`);
     }
   } catch (error) {
-    return { text: String(error), error: true };
+    // point to the script settings when the interpreter is not installed
+    if (errorKind(error) === 'RuntimeMissing') {
+      return { text: m.script_runtime_missing({ message: errorMessage(error) }), error: true };
+    }
+    return { text: errorMessage(error), error: true };
   }
 }
 
@@ -548,6 +552,6 @@ async function showPopup(entry: Entry, placement?: WindowPlacement): Promise<voi
       });
     }
   } catch (error) {
-    console.error(`Failed to show popup window: ${error}`);
+    console.error(`Failed to show popup window: ${errorMessage(error)}`);
   }
 }
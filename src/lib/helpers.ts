@@ -1,6 +1,7 @@
 import { DBCLICK_SHORTCUT, DRAG_SHORTCUT, SHIFT_CLICK_SHORTCUT } from '$lib/constants';
 import { m } from '$lib/paraglide/messages';
 import { getLocale, locales } from '$lib/paraglide/runtime';
+import type { AppError } from '$lib/types';
 import { invoke } from '@tauri-apps/api/core';
 import { type } from '@tauri-apps/plugin-os';
 import type { ActionReturn } from 'svelte/action';
@@ -97,6 +98,32 @@ export function formatShortcut(shortcut: string): string {
     .join(' + ');
 }
 
+/**
+ * Get the message of an error thrown by a backend command.
+ *
+ * @param error - thrown error (backend errors are `{ kind, message }` objects)
+ * @returns error message
+ */
+export function errorMessage(error: unknown): string {
+  if (error && typeof error === 'object' && 'message' in error) {
+    return String(error.message);
+  }
+  return String(error);
+}
+
+/**
+ * Get the kind of an error thrown by a backend command.
+ *
+ * @param error - thrown error
+ * @returns error kind, or undefined if it is not a backend error
+ */
+export function errorKind(error: unknown): AppError['kind'] | undefined {
+  if (error && typeof error === 'object' && 'kind' in error) {
+    return error.kind as AppError['kind'];
+  }
+  return undefined;
+}
+
 /**
  * Format ISO8601 datetime string.
  *
@@ -162,10 +189,11 @@ export function tooltip(target: HTMLElement, props: Partial<Props>): ActionRetur
 }
 
 /**
- * Setup tray menu language.
+ * Setup tray menu and backend display language.
  */
 export async function setupTray() {
   try {
+    await invoke('set_locale', { locale: getLocale() });
     await invoke('setup_tray', {
       mainWindowText: m.tray_main_window(),
       shortcutsText: m.tray_shortcuts(),
@@ -174,7 +202,7 @@ export async function setupTray() {
       quitText: m.tray_quit()
     });
   } catch (error) {
-    console.error(`Failed to setup tray menu language: ${error}`);
+    console.error(`Failed to setup tray menu language: ${errorMessage(error)}`);
   }
 }
 
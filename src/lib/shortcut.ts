@@ -1,6 +1,9 @@
+import { alert } from '$lib/components/Alert.svelte';
 import { execute } from '$lib/executor';
+import { errorKind, errorMessage } from '$lib/helpers';
+import { m } from '$lib/paraglide/messages';
 import { shortcuts } from '$lib/stores.svelte';
-import type { Rule } from '$lib/types';
+import type { CapturedSelection, Rule } from '$lib/types';
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
 import { getCurrentWindow } from '@tauri-apps/api/window';
@@ -51,6 +54,9 @@ export function updateActionId(prefix: string, actionId: string, newActionId: st
  * Shortcut manager class.
  */
 export class Manager {
+  // whether the user has been guided to grant accessibility permission
+  private permissionGuided = false;
+
   constructor() {
     this.initialize();
   }
@@ -63,11 +69,18 @@ export class Manager {
       try {
         // listen for shortcut triggered events from Rust backend
         await listen('shortcut', async (event) => {
-          const payload = event.payload as { shortcut: string; selection: string };
+          const payload = event.payload as { shortcut: string; selection: string; truncated?: boolean };
+          if (payload.truncated) {
+            console.warn(`Selection truncated to the maximum length for shortcut ${payload.shortcut}`);
+          }
           await this.handleShortcutEvent(payload.shortcut, payload.selection);
         });
+        // listen for selection capture failures from Rust backend
+        await listen('selection-error', async (event) => {
+          await this.handleCaptureError(event.payload);
+        });
       } catch (error) {
-        console.error(`Failed to initialize shortcut event listener: ${error}`);
+        console.error(`Failed to initialize shortcut event listener: ${errorMessage(error)}`);
       }
     }
   }
@@ -98,7 +111,8 @@ export class Manager {
       // fetch selection for mouse shortcuts if not provided
       const mouse = isMouseShortcut(shortcut);
       if (mouse && !selection.trim()) {
-        selection = await invoke<string>('get_selection', { mouse: true });
+        const captured = await invoke<CapturedSelection>('get_selection', { mouse: true });
+        selection = captured.text;
         if (!selection.trim()) {
           return;
         }
@@ -134,7 +148,26 @@ export class Manager {
         await execute(rule, selection);
       }
     } catch (error) {
-      console.error(`Failed to handle shortcut event: ${error}`);
+      console.error(`Failed to handle shortcut event: ${errorMessage(error)}`);
+    }
+  }
+
+  /**
+   * Handle selection capture error.
+   *
+   * @param error - error thrown by the backend
+   */
+  private async handleCaptureError(error: unknown): Promise<void> {
+    console.error(`Failed to capture selection: ${errorMessage(error)}`);
+    // guide the user to grant accessibility permission once per session
+    if (errorKind(error) === 'PermissionDenied' && !this.permissionGuided) {
+      this.permissionGuided = true;
+      try {
+        await invoke('navigate_to', { url: '/settings/general' });
+        alert({ level: 'error', message: m.accessibility_required() });
+      } catch (error) {
+        console.error(`Failed to show accessibility settings: ${errorMessage(error)}`);
+      }
     }
   }
 
@@ -160,7 +193,7 @@ export class Manager {
         s.rules.push(rule);
       }
     } catch (error) {
-      console.error(`Failed to register rule: ${error}`);
+      console.error(`Failed to register rule: ${errorMessage(error)}`);
       throw error;
     }
   }
@@ -186,7 +219,7 @@ export class Manager {
         }
       }
     } catch (error) {
-      console.error(`Failed to unregister rule: ${error}`);
+      console.error(`Failed to unregister rule: ${errorMessage(error)}`);
       throw error;
     }
   }
@@ -1,12 +1,14 @@
 import {
   DEFAULT_POPUP_WINDOW_SIZE,
   POPUP_CORNER_RADIUS,
+  POPUP_OPACITY,
   TOOLBAR_ACTION_COUNT,
   TOOLBAR_CORNER_RADIUS,
   TOOLBAR_OPACITY
 } from '$lib/constants';
 import type {
   CustomLLMProvider,
+  DndSchedule,
   Entry,
   Model,
   Prompt,
@@ -19,6 +21,7 @@ import type {
 import { decrypt, encrypt } from '$lib/utils';
 import { invoke } from '@tauri-apps/api/core';
 import { getCurrentWindow, type Theme } from '@tauri-apps/api/window';
+import { type } from '@tauri-apps/plugin-os';
 import { LazyStore } from '@tauri-apps/plugin-store';
 import { debounce } from 'es-toolkit/function';
 import { tick, untrack } from 'svelte';
@@ -174,6 +177,17 @@ export const autoUpdate = persisted<boolean>('autoUpdate', false);
 // minimize to tray setting
 export const minimizeToTray = persisted<boolean>('minimizeToTray', false);
 
+// action performed when the tray icon is left-clicked
+export const trayLeftClickAction = persisted<'menu' | 'toggle' | 'popup'>(
+  'trayLeftClickAction',
+  type() === 'windows' ? 'popup' : 'menu',
+  {
+    onchange: (action) => {
+      invoke('set_tray_left_click_action', { action });
+    }
+  }
+);
+
 // accessibility permission granted
 export const accessibility = persisted<boolean>('accessibility', false);
 
@@ -189,6 +203,9 @@ export const toolbarOpacity = persisted<number>('toolbarOpacity', TOOLBAR_OPACIT
 // popup corner radius in pixels
 export const popupCornerRadius = persisted<number>('popupCornerRadius', POPUP_CORNER_RADIUS.default);
 
+// popup window opacity
+export const popupOpacity = persisted<number>('popupOpacity', POPUP_OPACITY.default);
+
 // whether the popup window is pinned
 export const popupPinned = persisted<boolean>('popupPinned', false);
 
@@ -219,6 +236,83 @@ export const iBeamCursor = persisted<boolean>('iBeamCursor', true, {
   }
 });
 
+// maximum number of characters kept from a captured selection (0 means unlimited)
+export const maxSelectionChars = persisted<number>('maxSelectionChars', 100000);
+
+// whether to remove zero-width, control and BiDi characters from captured selection
+export const sanitizeSelection = persisted<boolean>('sanitizeSelection', false);
+
+// seconds before the last captured selection is considered stale (0 means never)
+export const lastSelectionExpiry = persisted<number>('lastSelectionExpiry', 300, {
+  onchange: (seconds) => {
+    invoke('set_last_selection_expiry', { seconds });
+  }
+});
+
+// global hotkey that captures the selection without input monitoring permission (empty to disable)
+export const captureHotkey = persisted<string>('captureHotkey', '', {
+  onchange: (shortcut) => {
+    invoke('set_capture_hotkey', { shortcut: shortcut || null });
+  }
+});
+
+// key code that dismisses the popup and toolbar (empty to disable)
+export const dismissKey = persisted<string>('dismissKey', 'Escape', {
+  onchange: (key) => {
+    invoke('set_dismiss_key', { key });
+  }
+});
+
+// whether to keep the popup open after entering text for chained edits
+export const keepPopupOpen = persisted<boolean>('keepPopupOpen', false);
+
+// whether to paste AI responses back into the original application when they finish
+export const autoPasteResponse = persisted<boolean>('autoPasteResponse', false);
+
+// whether to release held modifier keys before sending copy/paste shortcuts
+export const releaseModifiersBeforeShortcut = persisted<boolean>('releaseModifiersBeforeShortcut', true, {
+  onchange: (enabled) => {
+    invoke('set_release_modifiers_enabled', { enabled });
+  }
+});
+
+// seconds to cache accessibility API enabling per process (0 disables caching)
+export const axapiCacheDuration = persisted<number>('axapiCacheDuration', 5, {
+  onchange: (seconds) => {
+    invoke('set_axapi_cache_duration', { seconds });
+  }
+});
+
+// whether to raise thread priority while waiting for copied text on busy systems
+export const boostCapturePriority = persisted<boolean>('boostCapturePriority', false);
+
+// whether to silently fall back to clipboard capture without accessibility permission
+export const accessibilityFallback = persisted<boolean>('accessibilityFallback', false);
+
+// selection capture method overrides keyed by application wildcard pattern
+export const selectionMethodOverrides = persisted<Record<string, 'native' | 'clipboard'>>(
+  'selectionMethodOverrides',
+  {}
+);
+
+// copy shortcut overrides keyed by application wildcard pattern (e.g. Control+Shift+KeyC)
+export const copyKeysOverrides = persisted<Record<string, string>>('copyKeysOverrides', {});
+
+// paste shortcut overrides keyed by application wildcard pattern (e.g. Control+Shift+KeyV)
+export const pasteKeysOverrides = persisted<Record<string, string>>('pasteKeysOverrides', {});
+
+// additional terminal application wildcard patterns, captured through clipboard
+export const terminalApps = persisted<string[]>('terminalApps', []);
+
+// application wildcard patterns whose native selection is skipped in favor of the clipboard
+export const forceFallbackApps = persisted<string[]>('forceFallbackApps', []);
+
+// force text fields editable keyed by application wildcard pattern, for custom-widget apps
+export const editableOverrides = persisted<Record<string, boolean>>('editableOverrides', {});
+
+// do-not-disturb schedule for pausing shortcut handling
+export const dndSchedule = persisted<DndSchedule>('dndSchedule', { mode: 'active', rules: [] });
+
 // shortcut trigger records
 export const entries = persisted<Entry[]>('entries', []);
 
@@ -20,6 +20,32 @@ export type WindowSize = {
   height: number;
 };
 
+/**
+ * Error returned by backend commands.
+ */
+export type AppError = {
+  kind: 'PermissionDenied' | 'RuntimeMissing' | 'NotFound' | 'Timeout' | 'Other';
+  message: string;
+};
+
+/**
+ * Selected text returned by `get_selection`, cut to the `maxSelectionChars` setting.
+ * Truncated text ends with a localized marker such as `…[truncated 42 chars]`.
+ */
+export type CapturedSelection = {
+  text: string;
+  truncated: boolean;
+  droppedChars: number;
+};
+
+/**
+ * Do-not-disturb schedule, time windows are "HH:MM" on ISO weekdays (1 = Monday).
+ */
+export type DndSchedule = {
+  mode: 'active' | 'paused';
+  rules: { days: number[]; start: string; end: string }[];
+};
+
 /**
  * Convert all properties in type T to nullable.
  */